@@ -2,8 +2,9 @@
 extern crate sdl2_engine as engine;
 
 use engine::{ Renderer, Scene, Entity, };
-use engine::input::{ Key };
+use engine::input::{ Axis, Binding, Key, Scancode };
 use engine::sprites::{ SpriteAnimation, SpriteBuilder };
+use engine::timer::{ StatsOverlay };
 use engine::transform::{ Transform };
 
 
@@ -60,41 +61,40 @@ fn main() -> Result<(), String> {
             }
 
             swarm.populate(&populous);
+            swarm.properties.timer.stats.overlay = StatsOverlay::WindowTitle;
+
+            let cursor = swarm.properties.input.cursor.load("assets/cursor.png", 0, 0).ok();
+            swarm.properties.input.cursor.set(cursor);
+
+            let actions = &mut swarm.properties.input.actions;
+            actions.bind("camera_x", Binding::Scancode(Scancode::D));
+            actions.bind_negative("camera_x", Binding::Scancode(Scancode::A));
+            actions.bind_axis("camera_x", Axis::RightX);
+            actions.bind("camera_y", Binding::Scancode(Scancode::S));
+            actions.bind_negative("camera_y", Binding::Scancode(Scancode::W));
+            actions.bind_axis("camera_y", Axis::RightY);
+            actions.bind("camera_zoom", Binding::Scancode(Scancode::Q));
+            actions.bind_negative("camera_zoom", Binding::Scancode(Scancode::E));
+
+            let report = actions.load("bindings.toml");
+            for error in report.errors.iter() {
+                println!("bindings.toml: {}", error);
+            }
         },
 
         |swarm| {
 
             let delta_time = swarm.properties.timer.delta_time as f32;
 
-            let cam_left = swarm.properties.input.keyboard.down(Key::A);
-            let cam_right = swarm.properties.input.keyboard.down(Key::D);
-            let cam_up = swarm.properties.input.keyboard.down(Key::W);
-            let cam_down = swarm.properties.input.keyboard.down(Key::S);
-            let cam_zoom_in = swarm.properties.input.keyboard.down(Key::E);
-            let cam_zoom_out = swarm.properties.input.keyboard.down(Key::Q);
-
-            if cam_left ^ cam_right {
-                if cam_left {
-                    swarm.properties.camera.x -= delta_time * 200.0;
-                } else if cam_right {
-                    swarm.properties.camera.x += delta_time * 200.0;     
-                }
-            }
-            
-            if cam_up ^ cam_down {
-                if cam_up {
-                    swarm.properties.camera.y -= delta_time * 200.0;
-                } else if cam_down {
-                    swarm.properties.camera.y += delta_time * 200.0;     
-                }
-            }
+            let cam_x = swarm.properties.input.actions.value("camera_x");
+            let cam_y = swarm.properties.input.actions.value("camera_y");
+            let cam_zoom = swarm.properties.input.actions.value("camera_zoom");
 
-            if cam_zoom_in ^ cam_zoom_out {
-                if cam_zoom_in {
-                    swarm.properties.camera.zoom -= delta_time * 0.1;
-                } else if cam_zoom_out {
-                    swarm.properties.camera.zoom += delta_time * 0.1;    
-                }
+            swarm.properties.camera_mut().x += cam_x * delta_time * 200.0;
+            swarm.properties.camera_mut().y += cam_y * delta_time * 200.0;
+
+            if cam_zoom != 0.0 {
+                swarm.properties.camera_mut().zoom_by(1.0 + cam_zoom * delta_time);
             }
 
 
@@ -191,7 +191,7 @@ static BABY_IDLE_ANIM: SpriteAnimation = SpriteAnimation {
     current_frame: 0,
     tile_range: (0..=0),
     millis_per_frame: 80,
-    millis_passed: 0,
+    millis_passed: 0.0,
 };
 
 
@@ -200,7 +200,7 @@ static BABY_WALK_ANIM: SpriteAnimation = SpriteAnimation {
     current_frame: 0,
     tile_range: (0..=3),
     millis_per_frame: 80,
-    millis_passed: 0,
+    millis_passed: 0.0,
 };
 
 
@@ -209,7 +209,7 @@ static KING_IDLE_ANIM: SpriteAnimation = SpriteAnimation {
     current_frame: 0,
     tile_range: (4..=4),
     millis_per_frame: 80,
-    millis_passed: 0,
+    millis_passed: 0.0,
 };
 
 const KING_WALK: usize = 3;
@@ -217,7 +217,7 @@ static KING_WALK_ANIM: SpriteAnimation = SpriteAnimation {
     current_frame: 0,
     tile_range: (4..=7),
     millis_per_frame: 80,
-    millis_passed: 0,
+    millis_passed: 0.0,
 };
 
 const SOLDIER_IDLE: usize = 4;
@@ -225,7 +225,7 @@ static SOLDIER_IDLE_ANIM: SpriteAnimation = SpriteAnimation {
     current_frame: 0,
     tile_range: (8..=8),
     millis_per_frame: 80,
-    millis_passed: 0,
+    millis_passed: 0.0,
 };
 
 const SOLDIER_WALK: usize = 5;
@@ -233,5 +233,5 @@ static SOLDIER_WALK_ANIM: SpriteAnimation = SpriteAnimation {
     current_frame: 0,
     tile_range: (8..=11),
     millis_per_frame: 80,
-    millis_passed: 0,
+    millis_passed: 0.0,
 };
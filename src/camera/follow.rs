@@ -0,0 +1,145 @@
+use crate::swarm::Spawn;
+
+
+#[derive(Clone)]
+pub enum Smoothing {
+    // snap to the target position
+    None,
+    // move a fraction of the remaining distance, higher speeds catch up faster
+    Lerp(f32),
+    // a damped spring, slightly overshoots with a low damping value
+    Spring { stiffness: f32, damping: f32 },
+}
+
+
+#[derive(Clone)]
+pub struct WorldBounds {
+    pub left: f32,
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+}
+
+
+#[derive(Clone)]
+pub struct CameraFollow {
+    pub target: Spawn,
+    // half the size of the area the target can move in without moving the camera
+    pub dead_zone: (f32, f32),
+    pub smoothing: Smoothing,
+    // how many seconds of target movement the camera looks ahead
+    pub look_ahead: f32,
+    pub offset: (f32, f32),
+    pub bounds: Option<WorldBounds>,
+
+    last_focus: Option<(f32, f32)>,
+    target_velocity: (f32, f32),
+    spring_velocity: (f32, f32),
+}
+
+impl CameraFollow {
+    pub fn new(target: Spawn) -> Self {
+        CameraFollow {
+            target,
+            dead_zone: (0.0, 0.0),
+            smoothing: Smoothing::Lerp(5.0),
+            look_ahead: 0.0,
+            offset: (0.0, 0.0),
+            bounds: None,
+
+            last_focus: None,
+            target_velocity: (0.0, 0.0),
+            spring_velocity: (0.0, 0.0),
+        }
+    }
+    pub fn with_dead_zone(mut self, half_width: f32, half_height: f32) -> Self {
+        self.dead_zone = (half_width, half_height);
+        self
+    }
+    pub fn with_smoothing(mut self, smoothing: Smoothing) -> Self {
+        self.smoothing = smoothing;
+        self
+    }
+    pub fn with_look_ahead(mut self, seconds: f32) -> Self {
+        self.look_ahead = seconds;
+        self
+    }
+    pub fn with_offset(mut self, x: f32, y: f32) -> Self {
+        self.offset = (x, y);
+        self
+    }
+    pub fn with_bounds(mut self, left: f32, top: f32, right: f32, bottom: f32) -> Self {
+        self.bounds = Some(WorldBounds { left, top, right, bottom });
+        self
+    }
+
+    // returns the new camera position, `view` is half the visible area in world units
+    pub(crate) fn update(
+        &mut self,
+        camera: (f32, f32),
+        focus: (f32, f32),
+        view: (f32, f32),
+        delta_time: f32,
+    ) -> (f32, f32) {
+        // estimate target velocity for looking ahead
+        if let Some(last) = self.last_focus {
+            if delta_time > 0.0 {
+                let t = 1.0 - (-10.0 * delta_time).exp();
+                self.target_velocity.0 += ((focus.0 - last.0) / delta_time - self.target_velocity.0) * t;
+                self.target_velocity.1 += ((focus.1 - last.1) / delta_time - self.target_velocity.1) * t;
+            }
+        }
+        self.last_focus = Some(focus);
+
+        let desired = (
+            focus.0 + self.offset.0 + self.target_velocity.0 * self.look_ahead,
+            focus.1 + self.offset.1 + self.target_velocity.1 * self.look_ahead,
+        );
+
+        // only move when the target leaves the dead zone
+        let goal = (
+            outside_dead_zone(camera.0, desired.0, self.dead_zone.0),
+            outside_dead_zone(camera.1, desired.1, self.dead_zone.1),
+        );
+
+        let mut position = match self.smoothing {
+            Smoothing::None => goal,
+            Smoothing::Lerp(speed) => {
+                let t = 1.0 - (-speed * delta_time).exp();
+                (camera.0 + (goal.0 - camera.0) * t, camera.1 + (goal.1 - camera.1) * t)
+            },
+            Smoothing::Spring { stiffness, damping } => {
+                let v = &mut self.spring_velocity;
+                v.0 += (stiffness * (goal.0 - camera.0) - damping * v.0) * delta_time;
+                v.1 += (stiffness * (goal.1 - camera.1) - damping * v.1) * delta_time;
+                (camera.0 + v.0 * delta_time, camera.1 + v.1 * delta_time)
+            },
+        };
+
+        // keep the view inside of the level
+        if let Some(bounds) = &self.bounds {
+            position.0 = clamp_view(position.0, bounds.left, bounds.right, view.0);
+            position.1 = clamp_view(position.1, bounds.top, bounds.bottom, view.1);
+        }
+        position
+    }
+}
+
+
+fn outside_dead_zone(camera: f32, target: f32, half_size: f32) -> f32 {
+    if target > camera + half_size {
+        target - half_size
+    } else if target < camera - half_size {
+        target + half_size
+    } else {
+        camera
+    }
+}
+
+fn clamp_view(position: f32, min: f32, max: f32, half_view: f32) -> f32 {
+    if max - min <= half_view * 2.0 {
+        (min + max) * 0.5
+    } else {
+        position.clamp(min + half_view, max - half_view)
+    }
+}
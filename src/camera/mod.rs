@@ -0,0 +1,196 @@
+mod follow;
+mod shake;
+mod zoom;
+
+use sdl2::rect::Rect;
+
+use crate::renderer::Screen;
+pub use follow::{ CameraFollow, Smoothing, WorldBounds };
+pub use shake::CameraShake;
+use zoom::ZoomAnimation;
+
+pub struct Camera {
+    pub x: f32,
+    pub y: f32,
+    // scale factor of the view, 2.0 draws everything twice as large and 0.5 half as large
+    pub zoom: f32,
+    pub min_zoom: f32,
+    pub max_zoom: f32,
+    // clockwise rotation of the world view around the screen center in degrees
+    pub rotation: f64,
+    pub follow: Option<CameraFollow>,
+    pub shake: CameraShake,
+    // area of the window this camera draws in, None uses the whole window
+    pub viewport: Option<Rect>,
+    // bit mask of the sprite layers this camera draws, all layers by default
+    pub layers: u32,
+    // the light map is multiplied over what this camera draws, turn off for HUD cameras
+    pub lit: bool,
+    zoom_animation: Option<ZoomAnimation>,
+}
+
+impl Camera {
+    pub fn new() -> Self {
+        Camera { 
+            x: 0.0, 
+            y: 0.0, 
+            zoom: 1.0, 
+            min_zoom: 0.1, 
+            max_zoom: 10.0, 
+            rotation: 0.0,
+            follow: None, 
+            shake: CameraShake::new(), 
+            viewport: None,
+            layers: u32::MAX,
+            lit: true,
+            zoom_animation: None,
+        }
+    }
+
+    pub fn with_viewport(mut self, x: i32, y: i32, width: u32, height: u32) -> Self {
+        self.viewport = Some(Rect::new(x, y, width, height));
+        self
+    }
+    pub fn with_layers(mut self, layers: u32) -> Self {
+        self.layers = layers;
+        self
+    }
+    pub fn without_lighting(mut self) -> Self {
+        self.lit = false;
+        self
+    }
+
+    // the area of the window this camera draws in
+    pub fn view_rect(&self, screen: &Screen) -> Rect {
+        self.viewport.unwrap_or_else(|| Rect::new(0, 0, screen.width, screen.height))
+    }
+
+    pub fn shows_layer(&self, layer: u8) -> bool {
+        layer < 32 && self.layers & (1 << layer) != 0
+    }
+    // layers from 32 up do not exist and are ignored
+    pub fn show_layer(&mut self, layer: u8) {
+        self.layers |= 1u32.checked_shl(layer as u32).unwrap_or(0);
+    }
+    pub fn hide_layer(&mut self, layer: u8) {
+        self.layers &= !1u32.checked_shl(layer as u32).unwrap_or(0);
+    }
+
+    // `focus` is the center of the followed entity, or None if there is nothing to follow
+    pub(crate) fn update(&mut self, focus: Option<(f32, f32)>, screen: &Screen, delta_time: f32) {
+        if let Some(animation) = &mut self.zoom_animation {
+            let (zoom, finished) = animation.advance(delta_time);
+            let anchor = animation.anchor;
+            if finished { self.zoom_animation = None; }
+
+            match anchor {
+                Some((x, y)) => self.zoom_at(zoom, screen, x, y),
+                None => self.zoom = zoom,
+            }
+        }
+
+        let scale = self.scale();
+        let rect = self.view_rect(screen);
+        if let (Some(follow), Some(focus)) = (&mut self.follow, focus) {
+            // half the size of the visible area, rotated views show more of the world
+            let (sin, cos) = self.rotation.to_radians().sin_cos();
+            let (sin, cos) = (sin.abs() as f32, cos.abs() as f32);
+            let (width, height) = (rect.width() as f32 * 0.5 / scale, rect.height() as f32 * 0.5 / scale);
+            let view = (width * cos + height * sin, width * sin + height * cos);
+            let (x, y) = follow.update((self.x, self.y), focus, view, delta_time);
+            self.x = x;
+            self.y = y;
+        }
+        self.shake.update(delta_time);
+    }
+
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.shake.add_trauma(amount);
+    }
+
+    // rotation in degrees the world is drawn with, sprites add this to their own rotation
+    pub fn view_angle(&self) -> f64 {
+        self.rotation + self.shake.angle
+    }
+
+    // the number of screen pixels a world unit covers, which is the zoom kept within its limits
+    pub fn scale(&self) -> f32 {
+        self.limit_zoom(self.zoom)
+    }
+
+    // multiplies the zoom, a factor of 1.1 zooms in by 10%
+    pub fn zoom_by(&mut self, factor: f32) {
+        self.zoom = self.limit_zoom(self.zoom * factor);
+    }
+
+    // Sets the zoom while keeping the world position under a screen position, like the 
+    // mouse cursor, in the same place.
+    pub fn zoom_at(&mut self, zoom: f32, screen: &Screen, x: i32, y: i32) {
+        let before = self.screen_to_world(screen, x, y);
+        self.zoom = self.limit_zoom(zoom);
+        let after = self.screen_to_world(screen, x, y);
+
+        self.x += before.0 - after.0;
+        self.y += before.1 - after.1;
+    }
+
+    // animates the zoom towards `zoom` over `duration` seconds
+    pub fn zoom_to(&mut self, zoom: f32, duration: f32) {
+        self.start_zoom_animation(zoom, duration, None);
+    }
+
+    // animates the zoom while keeping the world position under a screen position in place
+    pub fn zoom_to_at(&mut self, zoom: f32, duration: f32, x: i32, y: i32) {
+        self.start_zoom_animation(zoom, duration, Some((x, y)));
+    }
+
+    pub fn zooming(&self) -> bool {
+        self.zoom_animation.is_some()
+    }
+
+    fn limit_zoom(&self, zoom: f32) -> f32 {
+        zoom.max(self.min_zoom).min(self.max_zoom)
+    }
+
+    fn start_zoom_animation(&mut self, zoom: f32, duration: f32, anchor: Option<(i32, i32)>) {
+        self.zoom_animation = Some(ZoomAnimation {
+            from: self.scale(),
+            to: self.limit_zoom(zoom),
+            duration,
+            elapsed: 0.0,
+            anchor,
+        });
+    }
+
+    // Converts a world position into a screen position, this is the same projection
+    // the renderer uses to draw entities and includes the current screen shake. Screen 
+    // positions are window positions in the canvas' logical coordinates, which are also 
+    // the coordinates mouse events are reported in.
+    pub fn world_to_screen(&self, screen: &Screen, x: f32, y: f32) -> (f32, f32) {
+        let center = self.view_rect(screen).center();
+        let scale = self.scale();
+        let (sin, cos) = self.view_angle().to_radians().sin_cos();
+        let (x, y) = ((x - self.x) * scale, (y - self.y) * scale);
+        (
+            center.x() as f32 + self.shake.offset.0 + x * cos as f32 - y * sin as f32,
+            center.y() as f32 + self.shake.offset.1 + x * sin as f32 + y * cos as f32,
+        )
+    }
+
+    // Converts a screen position, like the mouse cursor, into a world position.
+    pub fn screen_to_world(&self, screen: &Screen, x: i32, y: i32) -> (f32, f32) {
+        let center = self.view_rect(screen).center();
+        let scale = self.scale();
+        let (sin, cos) = self.view_angle().to_radians().sin_cos();
+        let x = (x - center.x()) as f32 - self.shake.offset.0;
+        let y = (y - center.y()) as f32 - self.shake.offset.1;
+        (
+            self.x + (x * cos as f32 + y * sin as f32) / scale,
+            self.y + (y * cos as f32 - x * sin as f32) / scale,
+        )
+    }
+}
+
+impl Default for Camera {
+    fn default() -> Self { Camera::new() }
+}
@@ -0,0 +1,60 @@
+use crate::random::noise;
+
+
+// Trauma based screen shake, the shake strength is trauma squared so small hits
+// barely move the view while big ones shake it hard.
+#[derive(Clone)]
+pub struct CameraShake {
+    // 0.0 is calm and 1.0 is the strongest shake
+    pub trauma: f32,
+    // trauma removed per second
+    pub decay: f32,
+    // largest offset in screen pixels
+    pub max_offset: (f32, f32),
+    // largest rotation in degrees
+    pub max_angle: f64,
+    // how fast the shake changes direction
+    pub frequency: f32,
+
+    time: f32,
+    pub(crate) offset: (f32, f32),
+    pub(crate) angle: f64,
+}
+
+impl CameraShake {
+    pub fn new() -> Self {
+        CameraShake {
+            trauma: 0.0,
+            decay: 1.0,
+            max_offset: (20.0, 20.0),
+            max_angle: 5.0,
+            frequency: 15.0,
+
+            time: 0.0,
+            offset: (0.0, 0.0),
+            angle: 0.0,
+        }
+    }
+
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount).clamp(0.0, 1.0);
+    }
+
+    pub(crate) fn update(&mut self, delta_time: f32) {
+        self.trauma = (self.trauma - self.decay * delta_time).max(0.0);
+        self.time += delta_time;
+
+        let shake = self.trauma * self.trauma;
+        let t = self.time * self.frequency;
+
+        self.offset = (
+            self.max_offset.0 * shake * noise(1, t),
+            self.max_offset.1 * shake * noise(2, t),
+        );
+        self.angle = self.max_angle * (shake * noise(3, t)) as f64;
+    }
+}
+
+impl Default for CameraShake {
+    fn default() -> Self { CameraShake::new() }
+}
@@ -0,0 +1,24 @@
+// an animated change of zoom, optionally keeping a screen position in place
+#[derive(Clone)]
+pub(crate) struct ZoomAnimation {
+    pub(crate) from: f32,
+    pub(crate) to: f32,
+    pub(crate) duration: f32,
+    pub(crate) elapsed: f32,
+    pub(crate) anchor: Option<(i32, i32)>,
+}
+
+impl ZoomAnimation {
+    // returns the zoom for this frame and whether the animation has finished
+    pub(crate) fn advance(&mut self, delta_time: f32) -> (f32, bool) {
+        self.elapsed += delta_time;
+
+        if self.elapsed >= self.duration {
+            return (self.to, true);
+        }
+        // ease in and out, interpolated on a log scale so each step feels the same size
+        let t = self.elapsed / self.duration;
+        let t = t * t * (3.0 - 2.0 * t);
+        ((self.from.ln() + (self.to.ln() - self.from.ln()) * t).exp(), false)
+    }
+}
@@ -0,0 +1,188 @@
+use sdl2::controller::{ Axis, Button };
+use sdl2::keyboard::Scancode;
+use sdl2::mouse::MouseButton;
+
+use super::{ ControllerList, Key, KeyboardInput, MouseInput };
+
+
+// digital actions are down while their value is at least this far from zero
+const DOWN_THRESHOLD: f32 = 0.5;
+
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum AxisDirection { Positive, Negative }
+
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Binding {
+    Key(Key),
+    Scancode(Scancode),
+    Mouse(MouseButton),
+    ControllerButton(Button),
+    // one direction of a controller axis, gives 0.0 to 1.0 as it is pushed further
+    ControllerAxis(Axis, AxisDirection),
+}
+
+
+struct Action {
+    name: String,
+    // every binding adds its value times the scale, -1.0 for the negative side of an axis
+    bindings: Vec<(Binding, f32)>,
+    value: f32,
+    previous: f32,
+}
+
+
+// Named actions bound to any number of inputs. Game code asks for the state of an
+// action, like "jump" or "move_x", instead of specific keys or buttons.
+pub struct ActionMap {
+    actions: Vec<Action>,
+}
+
+impl ActionMap {
+    pub fn new() -> Self {
+        ActionMap { actions: Vec::new() }
+    }
+
+    pub fn bind(&mut self, action: &str, binding: Binding) {
+        self.add_binding(action, binding, 1.0);
+    }
+
+    // binds an input that pushes an analog action towards -1.0, like the left key of "move_x"
+    pub fn bind_negative(&mut self, action: &str, binding: Binding) {
+        self.add_binding(action, binding, -1.0);
+    }
+
+    // binds both directions of a controller axis to an analog action
+    pub fn bind_axis(&mut self, action: &str, axis: Axis) {
+        self.add_binding(action, Binding::ControllerAxis(axis, AxisDirection::Positive), 1.0);
+        self.add_binding(action, Binding::ControllerAxis(axis, AxisDirection::Negative), -1.0);
+    }
+
+    pub fn unbind(&mut self, action: &str, binding: Binding) {
+        if let Some(action) = self.find_mut(action) {
+            action.bindings.retain(|b| b.0 != binding);
+        }
+    }
+
+    // replaces a binding, keeping the direction it was bound with
+    pub fn rebind(&mut self, action: &str, old: Binding, new: Binding) {
+        if let Some(action) = self.find_mut(action) {
+            for bound in action.bindings.iter_mut().filter(|b| b.0 == old) {
+                bound.0 = new;
+            }
+        }
+    }
+
+    // removes all bindings of an action
+    pub fn clear(&mut self, action: &str) {
+        if let Some(action) = self.find_mut(action) {
+            action.bindings.clear();
+        }
+    }
+
+    pub fn remove(&mut self, action: &str) {
+        self.actions.retain(|a| a.name != action);
+    }
+
+    pub fn bindings(&self, action: &str) -> Vec<(Binding, f32)> {
+        self.find(action).map(|a| a.bindings.clone()).unwrap_or_default()
+    }
+
+    pub fn actions(&self) -> impl Iterator<Item = &str> {
+        self.actions.iter().map(|a| a.name.as_str())
+    }
+
+    // the analog value of an action, from -1.0 to 1.0
+    pub fn value(&self, action: &str) -> f32 {
+        self.find(action).map_or(0.0, |a| a.value)
+    }
+    pub fn pressed(&self, action: &str) -> bool {
+        self.find(action).is_some_and(|a| is_down(a.value) && !is_down(a.previous))
+    }
+    pub fn down(&self, action: &str) -> bool {
+        self.find(action).is_some_and(|a| is_down(a.value))
+    }
+    pub fn released(&self, action: &str) -> bool {
+        self.find(action).is_some_and(|a| !is_down(a.value) && is_down(a.previous))
+    }
+
+    pub(crate) fn update(&mut self, keyboard: &KeyboardInput, mouse: &MouseInput, controllers: &ControllerList) {
+        for action in self.actions.iter_mut() {
+            let value: f32 = action.bindings.iter()
+                .map(|(binding, scale)| binding_value(binding, keyboard, mouse, controllers) * scale)
+                .sum();
+
+            action.value = value.clamp(-1.0, 1.0);
+        }
+    }
+
+    pub(crate) fn release_all(&mut self) {
+        for action in self.actions.iter_mut() {
+            action.value = 0.0;
+        }
+    }
+
+    // called after an update saw the current values, edges are measured from here
+    pub(crate) fn releave_activity(&mut self) {
+        for action in self.actions.iter_mut() {
+            action.previous = action.value;
+        }
+    }
+
+    fn add_binding(&mut self, action: &str, binding: Binding, scale: f32) {
+        if self.find(action).is_none() {
+            self.actions.push(Action { name: String::from(action), bindings: Vec::new(), value: 0.0, previous: 0.0 });
+        }
+        let action = self.find_mut(action).unwrap();
+        if !action.bindings.iter().any(|b| b.0 == binding && b.1 == scale) {
+            action.bindings.push((binding, scale));
+        }
+    }
+
+    fn find(&self, action: &str) -> Option<&Action> {
+        self.actions.iter().find(|a| a.name == action)
+    }
+
+    fn find_mut(&mut self, action: &str) -> Option<&mut Action> {
+        self.actions.iter_mut().find(|a| a.name == action)
+    }
+}
+
+impl Default for ActionMap {
+    fn default() -> Self { ActionMap::new() }
+}
+
+
+fn is_down(value: f32) -> bool {
+    value.abs() >= DOWN_THRESHOLD
+}
+
+fn digital(down: bool) -> f32 {
+    if down { 1.0 } else { 0.0 }
+}
+
+// the value of a single binding from 0.0 to 1.0, controllers use the strongest of all
+// connected controllers after their dead zones
+fn binding_value(binding: &Binding, keyboard: &KeyboardInput, mouse: &MouseInput, controllers: &ControllerList) -> f32 {
+    match *binding {
+        Binding::Key(key) => digital(keyboard.down(key)),
+        Binding::Scancode(scancode) => digital(keyboard.scancode_down(scancode)),
+        Binding::Mouse(button) => digital(mouse.down(button)),
+        Binding::ControllerButton(button) => {
+            digital(controllers.iter().any(|c| c.button(button)))
+        },
+        Binding::ControllerAxis(axis, direction) => {
+            controllers.iter()
+                .map(|c| {
+                    let value = c.axis_value(axis);
+                    match direction {
+                        AxisDirection::Positive => value.max(0.0),
+                        AxisDirection::Negative => (-value).max(0.0),
+                    }
+                })
+                .fold(0.0, f32::max)
+                .min(1.0)
+        },
+    }
+}
@@ -0,0 +1,277 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use sdl2::controller::{ Axis, Button };
+use sdl2::keyboard::Scancode;
+use sdl2::mouse::MouseButton;
+use toml::Value;
+
+use super::{ ActionMap, AxisDirection, Binding, Key };
+
+
+// A binding that is used by more than one action.
+#[derive(Clone, Debug)]
+pub struct BindingConflict {
+    pub binding: Binding,
+    pub actions: Vec<String>,
+}
+
+
+// The outcome of loading bindings. Entries that could not be used are listed in
+// `errors` and left at the bindings the game set up before loading.
+#[derive(Clone, Debug, Default)]
+pub struct BindingReport {
+    pub errors: Vec<String>,
+    pub conflicts: Vec<BindingConflict>,
+}
+
+
+// Bindings are stored as a table of action names with a list of inputs each:
+//
+//     [actions]
+//     jump = ["Key:Space", "Button:a"]
+//     move_x = ["Key:Right", "-Key:Left", "Axis:leftx+", "-Axis:leftx-"]
+//
+// a leading minus binds the negative side of an analog action.
+impl ActionMap {
+    // Replaces the bindings of every action found in the file. Bind the default
+    // controls before loading, actions missing from the file or without a single
+    // valid binding keep them. A missing file is not an error.
+    pub fn load<P: AsRef<Path>>(&mut self, path: P) -> BindingReport {
+        match fs::read_to_string(path.as_ref()) {
+            Ok(text) => self.load_str(&text),
+            Err(e) if e.kind() == ErrorKind::NotFound => BindingReport {
+                errors: Vec::new(),
+                conflicts: self.conflicts(),
+            },
+            Err(e) => BindingReport {
+                errors: vec![format!("{}: {}", path.as_ref().display(), e)],
+                conflicts: self.conflicts(),
+            },
+        }
+    }
+
+    pub fn load_str(&mut self, text: &str) -> BindingReport {
+        let mut report = BindingReport::default();
+
+        let table = match text.parse::<Value>() {
+            Ok(value) => value,
+            Err(e) => {
+                report.errors.push(e.to_string());
+                report.conflicts = self.conflicts();
+                return report;
+            }
+        };
+
+        match table.get("actions").and_then(|a| a.as_table()) {
+            Some(actions) => for (action, entries) in actions {
+                if !self.actions().any(|a| a == action) {
+                    report.errors.push(format!("unknown action '{}'", action));
+                    continue;
+                }
+
+                let entries = match entries.as_array() {
+                    Some(entries) => entries,
+                    None => {
+                        report.errors.push(format!("bindings of '{}' should be a list", action));
+                        continue;
+                    }
+                };
+
+                let mut bindings = Vec::new();
+                for entry in entries {
+                    match entry.as_str().and_then(parse_binding) {
+                        Some(binding) => bindings.push(binding),
+                        None => report.errors.push(format!("invalid binding {} for '{}'", entry, action)),
+                    }
+                }
+
+                if bindings.is_empty() && !entries.is_empty() {
+                    report.errors.push(format!("no valid bindings for '{}', using defaults", action));
+                    continue;
+                }
+
+                self.clear(action);
+                for (binding, scale) in bindings {
+                    if scale < 0.0 {
+                        self.bind_negative(action, binding);
+                    } else {
+                        self.bind(action, binding);
+                    }
+                }
+            },
+            None => report.errors.push(String::from("missing [actions] table")),
+        }
+
+        report.conflicts = self.conflicts();
+        report
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), String> {
+        fs::write(path.as_ref(), self.to_toml()).map_err(|e| e.to_string())
+    }
+
+    pub fn to_toml(&self) -> String {
+        let mut actions = toml::value::Table::new();
+        for action in self.actions() {
+            let bindings = self.bindings(action).into_iter()
+                .map(|(binding, scale)| Value::String(format_binding(binding, scale)))
+                .collect();
+            actions.insert(String::from(action), Value::Array(bindings));
+        }
+
+        let mut table = toml::value::Table::new();
+        table.insert(String::from("actions"), Value::Table(actions));
+        Value::Table(table).to_string()
+    }
+
+    // lists every binding that triggers more than one action
+    pub fn conflicts(&self) -> Vec<BindingConflict> {
+        let mut conflicts: Vec<BindingConflict> = Vec::new();
+
+        for action in self.actions() {
+            for (binding, _) in self.bindings(action) {
+                match conflicts.iter_mut().find(|c| c.binding == binding) {
+                    Some(c) => if !c.actions.iter().any(|a| a == action) {
+                        c.actions.push(String::from(action));
+                    },
+                    None => conflicts.push(BindingConflict { binding, actions: vec![String::from(action)] }),
+                }
+            }
+        }
+
+        conflicts.retain(|c| c.actions.len() > 1);
+        conflicts
+    }
+}
+
+
+fn format_binding(binding: Binding, scale: f32) -> String {
+    let sign = if scale < 0.0 { "-" } else { "" };
+    let input = match binding {
+        Binding::Key(key) => format!("Key:{}", key.name()),
+        Binding::Scancode(scancode) => format!("Scancode:{}", scancode.name()),
+        Binding::Mouse(button) => format!("Mouse:{}", mouse_button_name(button)),
+        Binding::ControllerButton(button) => format!("Button:{}", button.string()),
+        Binding::ControllerAxis(axis, AxisDirection::Positive) => format!("Axis:{}+", axis.string()),
+        Binding::ControllerAxis(axis, AxisDirection::Negative) => format!("Axis:{}-", axis.string()),
+    };
+    format!("{}{}", sign, input)
+}
+
+fn parse_binding(text: &str) -> Option<(Binding, f32)> {
+    let (text, scale) = match text.strip_prefix('-') {
+        Some(text) => (text, -1.0),
+        None => (text, 1.0),
+    };
+
+    let mut parts = text.splitn(2, ':');
+    let kind = parts.next()?;
+    let name = parts.next()?.trim();
+
+    let binding = match kind.trim() {
+        "Key" => Binding::Key(Key::from_name(name)?),
+        "Scancode" => Binding::Scancode(Scancode::from_name(name)?),
+        "Mouse" => Binding::Mouse(mouse_button_from_name(name)?),
+        "Button" => Binding::ControllerButton(Button::from_string(name)?),
+        "Axis" => {
+            let (axis, direction) = if let Some(axis) = name.strip_suffix('+') {
+                (axis, AxisDirection::Positive)
+            } else if let Some(axis) = name.strip_suffix('-') {
+                (axis, AxisDirection::Negative)
+            } else {
+                return None;
+            };
+            Binding::ControllerAxis(Axis::from_string(axis)?, direction)
+        },
+        _ => return None,
+    };
+    Some((binding, scale))
+}
+
+fn mouse_button_name(button: MouseButton) -> &'static str {
+    match button {
+        MouseButton::Left => "Left",
+        MouseButton::Middle => "Middle",
+        MouseButton::Right => "Right",
+        MouseButton::X1 => "X1",
+        MouseButton::X2 => "X2",
+        MouseButton::Unknown => "Unknown",
+    }
+}
+
+fn mouse_button_from_name(name: &str) -> Option<MouseButton> {
+    match name {
+        "Left" => Some(MouseButton::Left),
+        "Middle" => Some(MouseButton::Middle),
+        "Right" => Some(MouseButton::Right),
+        "X1" => Some(MouseButton::X1),
+        "X2" => Some(MouseButton::X2),
+        _ => None,
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game_actions() -> ActionMap {
+        let mut actions = ActionMap::new();
+        for action in ["jump", "move_x", "fire", "look"].iter() {
+            actions.bind(action, Binding::Key(Key::Unknown));
+        }
+        actions
+    }
+
+    #[test]
+    fn bindings_survive_a_round_trip() {
+        let mut saved = ActionMap::new();
+        saved.bind("jump", Binding::Key(Key::Space));
+        saved.bind("jump", Binding::ControllerButton(Button::A));
+        saved.bind("move_x", Binding::Key(Key::Right));
+        saved.bind_negative("move_x", Binding::Key(Key::Left));
+        saved.bind_axis("move_x", Axis::LeftX);
+        saved.bind("fire", Binding::Mouse(MouseButton::X1));
+        saved.bind("look", Binding::Scancode(Scancode::W));
+
+        let text = saved.to_toml();
+        assert!(text.contains("\"-Key:Left\""));
+        assert!(text.contains("\"Axis:leftx+\""));
+        assert!(text.contains("\"-Axis:leftx-\""));
+
+        let mut loaded = game_actions();
+        let report = loaded.load_str(&text);
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+        assert!(report.conflicts.is_empty());
+
+        for action in saved.actions() {
+            assert_eq!(saved.bindings(action), loaded.bindings(action), "{}", action);
+        }
+    }
+
+    #[test]
+    fn invalid_bindings_keep_the_defaults() {
+        let mut actions = game_actions();
+        let report = actions.load_str("[actions]\njump = [\"Key:NoSuchKey\"]\nfly = [\"Key:F\"]\n");
+
+        assert_eq!(report.errors.len(), 3);
+        assert_eq!(actions.bindings("jump"), vec![(Binding::Key(Key::Unknown), 1.0)]);
+        assert!(actions.bindings("fly").is_empty());
+
+        let report = actions.load_str("not toml at all [");
+        assert_eq!(report.errors.len(), 1);
+    }
+
+    #[test]
+    fn shared_bindings_are_reported_as_conflicts() {
+        let mut actions = game_actions();
+        let report = actions.load_str("[actions]\njump = [\"Key:Space\"]\nfire = [\"Key:Space\", \"Mouse:Left\"]\n");
+
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+        // the untouched defaults of move_x and look share Key::Unknown as well
+        let space = report.conflicts.iter().find(|c| c.binding == Binding::Key(Key::Space)).unwrap();
+        assert_eq!(space.actions, vec![String::from("jump"), String::from("fire")]);
+    }
+}
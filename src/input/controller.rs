@@ -1,37 +1,180 @@
-use sdl2::controller::Axis;
-use sdl2::controller::Button;
+use sdl2::controller::{ Axis, Button, GameController };
 
-pub struct ControllerList(Vec<ControllerInput>);
+use super::HapticPreset;
+
+// every SDL controller button, including paddles and the touchpad
+const BUTTON_COUNT: usize = 21;
+
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum DeadZoneShape {
+    // each axis of a stick has its own dead zone, keeps straight movement straight
+    Axial,
+    // the dead zone is a circle around the stick center, keeps diagonals smooth
+    Radial,
+}
+
+
+// Maps how far a stick or trigger is pushed, after the dead zone, to its value.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ResponseCurve {
+    Linear,
+    // finer control near the center, full speed at the edge
+    Quadratic,
+    Cubic,
+    Power(f32),
+}
+
+impl ResponseCurve {
+    fn apply(&self, t: f32) -> f32 {
+        match *self {
+            ResponseCurve::Linear => t,
+            ResponseCurve::Quadratic => t * t,
+            ResponseCurve::Cubic => t * t * t,
+            ResponseCurve::Power(power) => t.powf(power),
+        }
+    }
+}
+
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct ControllerSettings {
+    pub stick_dead_zone: f32,
+    pub dead_zone_shape: DeadZoneShape,
+    pub response_curve: ResponseCurve,
+    pub trigger_dead_zone: f32,
+    // how far a trigger is pulled before it counts as pressed
+    pub trigger_threshold: f32,
+}
+
+impl ControllerSettings {
+    pub fn new() -> Self {
+        ControllerSettings {
+            stick_dead_zone: 0.15,
+            dead_zone_shape: DeadZoneShape::Radial,
+            response_curve: ResponseCurve::Linear,
+            trigger_dead_zone: 0.05,
+            trigger_threshold: 0.5,
+        }
+    }
+    pub fn with_stick_dead_zone(mut self, dead_zone: f32, shape: DeadZoneShape) -> Self {
+        self.stick_dead_zone = dead_zone;
+        self.dead_zone_shape = shape;
+        self
+    }
+    pub fn with_response_curve(mut self, curve: ResponseCurve) -> Self {
+        self.response_curve = curve;
+        self
+    }
+    pub fn with_trigger_dead_zone(mut self, dead_zone: f32) -> Self {
+        self.trigger_dead_zone = dead_zone;
+        self
+    }
+    pub fn with_trigger_threshold(mut self, threshold: f32) -> Self {
+        self.trigger_threshold = threshold;
+        self
+    }
+}
+
+impl Default for ControllerSettings {
+    fn default() -> Self { ControllerSettings::new() }
+}
+
+
+// Connected game controllers. Every controller gets the lowest free player slot when
+// it connects and keeps it until it disconnects.
+pub struct ControllerList(Vec<ControllerInput>, ControllerSettings);
 
 impl ControllerList {
     pub fn new() -> Self {
-        ControllerList(Vec::new())
+        ControllerList(Vec::new(), ControllerSettings::new())
+    }
+
+    // the settings of controllers that connect later, and of every connected controller
+    pub fn set_settings(&mut self, settings: ControllerSettings) {
+        for controller in self.0.iter_mut() {
+            controller.settings = settings.clone();
+        }
+        self.1 = settings;
+    }
+
+    pub fn settings(&self) -> &ControllerSettings {
+        &self.1
+    }
+
+    pub(crate) fn releave_activity(&mut self) {
+        for controller in self.0.iter_mut() {
+            controller.releave_activity();
+        }
     }
 
-    pub fn add(&mut self, id: u32) {
-        self.0.push(ControllerInput::new(id));
+    pub(crate) fn add(&mut self, controller: GameController, guid: String) {
+        let id = controller.instance_id();
+        if self.get(id).is_some() { return; }
+
+        let mut player = 0;
+        while self.player(player).is_some() { player += 1; }
+
+        let mut input = ControllerInput::new(id);
+        input.player = player;
+        input.name = controller.name();
+        input.guid = guid;
+        input.settings = self.1.clone();
+        input.controller = Some(controller);
+        self.0.push(input);
     }
 
-    pub fn remove(&mut self, id: u32) {
+    // dropping the controller closes the device
+    pub(crate) fn remove(&mut self, id: u32) {
         if let Some(index) = self.0.iter_mut().position(|x| x.id == id) 
         {
             self.0.remove(index);
         }
     }
 
-    pub fn set_axis(&mut self, id: u32, axis: &Axis, value: i16) {
+    pub(crate) fn set_axis(&mut self, id: u32, axis: &Axis, value: i16) {
         if let Some(index) = self.0.iter_mut().position(|x| x.id == id) 
         {
             self.0[index].set_axis(axis, value);
         }   
     }
 
-    pub fn set_button(&mut self, id: u32, button: &Button, value: bool) {
+    pub(crate) fn set_button(&mut self, id: u32, button: &Button, value: bool) {
         if let Some(index) = self.0.iter_mut().position(|x| x.id == id) 
         {
             self.0[index].set_button(button, value);
         }   
     }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ControllerInput> {
+        self.0.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    // the controller with an SDL instance id, as used in controller events
+    pub fn get(&self, id: u32) -> Option<&ControllerInput> {
+        self.0.iter().find(|c| c.id == id)
+    }
+
+    // the controller of a player, slots start at 0
+    pub fn player(&self, slot: usize) -> Option<&ControllerInput> {
+        self.0.iter().find(|c| c.player == slot)
+    }
+
+    pub fn player_mut(&mut self, slot: usize) -> Option<&mut ControllerInput> {
+        self.0.iter_mut().find(|c| c.player == slot)
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut ControllerInput> {
+        self.0.iter_mut()
+    }
 }
 
 pub struct ControllerInput {
@@ -59,11 +202,24 @@ pub struct ControllerInput {
     pub button_d_pad_down: bool,
     pub button_d_pad_left: bool,
     pub button_d_pad_right: bool,
+
+    // dead zones and response curve of this controller
+    pub settings: ControllerSettings,
+
+    // state of the buttons without a field above, like the paddles and touchpad
+    other_buttons: [bool; BUTTON_COUNT],
+    changed: [bool; BUTTON_COUNT],
+    triggers_were_down: (bool, bool),
+    player: usize,
+    name: String,
+    guid: String,
+    // keeps the device open while it is connected
+    controller: Option<GameController>,
 }
 
 impl ControllerInput {
 
-    pub fn new(id: u32) -> Self {
+    pub(crate) fn new(id: u32) -> Self {
         ControllerInput {
             id,
         
@@ -89,10 +245,33 @@ impl ControllerInput {
             button_d_pad_down: false,
             button_d_pad_left: false,
             button_d_pad_right: false,
+
+            settings: ControllerSettings::new(),
+
+            other_buttons: [false; BUTTON_COUNT],
+            changed: [false; BUTTON_COUNT],
+            triggers_were_down: (false, false),
+            player: 0,
+            name: String::new(),
+            guid: String::new(),
+            controller: None,
         }
     }
 
-    pub fn set_axis(&mut self, axis: &Axis, value: i16) {
+    pub fn player(&self) -> usize {
+        self.player
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    // identifies the controller model, stays the same between runs
+    pub fn guid(&self) -> &str {
+        &self.guid
+    }
+
+    pub(crate) fn set_axis(&mut self, axis: &Axis, value: i16) {
         match *axis {
             Axis::LeftX => self.axis_left_x = value,
             Axis::LeftY => self.axis_left_y = value,
@@ -104,7 +283,164 @@ impl ControllerInput {
         }
     }
 
-    pub fn set_button(&mut self, button: &Button, value: bool) {
+    pub fn axis(&self, axis: Axis) -> i16 {
+        match axis {
+            Axis::LeftX => self.axis_left_x,
+            Axis::LeftY => self.axis_left_y,
+            Axis::RightX => self.axis_right_x,
+            Axis::RightY => self.axis_right_y,
+            Axis::TriggerLeft => self.axis_trigger_left,
+            Axis::TriggerRight => self.axis_trigger_right,
+        }
+    }
+
+    pub fn button(&self, button: Button) -> bool {
+        match button {
+            Button::A => self.button_a,
+            Button::B => self.button_b,
+            Button::X => self.button_x,
+            Button::Y => self.button_y,
+            Button::Back => self.button_back,
+            Button::Guide => self.button_guide,
+            Button::Start => self.button_start,
+            Button::LeftStick => self.button_left_stick,
+            Button::RightStick => self.button_right_stick,
+            Button::LeftShoulder => self.button_left_shoulder,
+            Button::RightShoulder => self.button_right_shoulder,
+            Button::DPadUp => self.button_d_pad_up,
+            Button::DPadDown => self.button_d_pad_down,
+            Button::DPadLeft => self.button_d_pad_left,
+            Button::DPadRight => self.button_d_pad_right,
+            _ => self.other_buttons.get(button as usize).copied().unwrap_or(false),
+        }
+    }
+
+    pub(crate) fn releave_activity(&mut self) {
+        self.changed = [false; BUTTON_COUNT];
+        self.triggers_were_down = (self.trigger_down(Axis::TriggerLeft), self.trigger_down(Axis::TriggerRight));
+    }
+
+    pub fn pressed(&self, button: Button) -> bool {
+        self.button(button) && self.changed(button)
+    }
+    pub fn released(&self, button: Button) -> bool {
+        !self.button(button) && self.changed(button)
+    }
+
+    // stick positions from -1.0 to 1.0 after the dead zone and response curve
+    pub fn left_stick(&self) -> (f32, f32) {
+        self.stick(self.axis_left_x, self.axis_left_y)
+    }
+    pub fn right_stick(&self) -> (f32, f32) {
+        self.stick(self.axis_right_x, self.axis_right_y)
+    }
+
+    // sticks give -1.0 to 1.0, triggers 0.0 to 1.0
+    pub fn axis_value(&self, axis: Axis) -> f32 {
+        match axis {
+            Axis::LeftX => self.left_stick().0,
+            Axis::LeftY => self.left_stick().1,
+            Axis::RightX => self.right_stick().0,
+            Axis::RightY => self.right_stick().1,
+            Axis::TriggerLeft | Axis::TriggerRight => {
+                let value = normalize(self.axis(axis)).max(0.0);
+                self.settings.response_curve.apply(rescale(value, self.settings.trigger_dead_zone))
+            },
+        }
+    }
+
+    // triggers act as buttons once they are pulled past the trigger threshold
+    pub fn trigger_pressed(&self, trigger: Axis) -> bool {
+        self.trigger_down(trigger) && !self.trigger_was_down(trigger)
+    }
+    pub fn trigger_down(&self, trigger: Axis) -> bool {
+        match trigger {
+            Axis::TriggerLeft | Axis::TriggerRight => self.axis_value(trigger) >= self.settings.trigger_threshold,
+            _ => false,
+        }
+    }
+    pub fn trigger_released(&self, trigger: Axis) -> bool {
+        !self.trigger_down(trigger) && self.trigger_was_down(trigger)
+    }
+
+    pub fn has_rumble(&self) -> bool {
+        self.controller.as_ref().is_some_and(|c| c.has_rumble())
+    }
+
+    pub fn has_trigger_rumble(&self) -> bool {
+        self.controller.as_ref().is_some_and(|c| c.has_rumble_triggers())
+    }
+
+    // Starts the rumble motors, strengths go from 0.0 to 1.0. A new rumble replaces the
+    // running one. Returns false when the controller has no rumble motors.
+    pub fn rumble(&mut self, low_frequency: f32, high_frequency: f32, duration_ms: u32) -> bool {
+        match self.controller.as_mut() {
+            Some(controller) if controller.has_rumble() => controller.set_rumble(
+                to_motor_strength(low_frequency),
+                to_motor_strength(high_frequency),
+                duration_ms,
+            ).is_ok(),
+            _ => false,
+        }
+    }
+
+    pub fn stop_rumble(&mut self) {
+        self.rumble(0.0, 0.0, 0);
+        self.rumble_triggers(0.0, 0.0, 0);
+    }
+
+    // rumbles the motors inside of the triggers, returns false when the controller has none
+    pub fn rumble_triggers(&mut self, left: f32, right: f32, duration_ms: u32) -> bool {
+        match self.controller.as_mut() {
+            Some(controller) if controller.has_rumble_triggers() => controller.set_rumble_triggers(
+                to_motor_strength(left),
+                to_motor_strength(right),
+                duration_ms,
+            ).is_ok(),
+            _ => false,
+        }
+    }
+
+    pub fn play_haptic(&mut self, preset: HapticPreset) -> bool {
+        let (low_frequency, high_frequency, duration_ms) = preset.rumble();
+        self.rumble(low_frequency, high_frequency, duration_ms)
+    }
+
+    fn changed(&self, button: Button) -> bool {
+        self.changed.get(button as usize).copied().unwrap_or(false)
+    }
+
+    fn trigger_was_down(&self, trigger: Axis) -> bool {
+        match trigger {
+            Axis::TriggerLeft => self.triggers_were_down.0,
+            Axis::TriggerRight => self.triggers_were_down.1,
+            _ => false,
+        }
+    }
+
+    fn stick(&self, x: i16, y: i16) -> (f32, f32) {
+        let (x, y) = (normalize(x), normalize(y));
+        let dead_zone = self.settings.stick_dead_zone;
+        let curve = &self.settings.response_curve;
+
+        match self.settings.dead_zone_shape {
+            DeadZoneShape::Axial => (
+                x.signum() * curve.apply(rescale(x.abs(), dead_zone)),
+                y.signum() * curve.apply(rescale(y.abs(), dead_zone)),
+            ),
+            DeadZoneShape::Radial => {
+                let length = (x * x + y * y).sqrt();
+                if length <= dead_zone { return (0.0, 0.0); }
+
+                let scale = curve.apply(rescale(length.min(1.0), dead_zone)) / length;
+                (x * scale, y * scale)
+            },
+        }
+    }
+
+    pub(crate) fn set_button(&mut self, button: &Button, value: bool) {
+        let changed = self.button(*button) != value;
+        if let Some(slot) = self.changed.get_mut(*button as usize) { *slot |= changed; }
         match *button {
             Button::A => self.button_a = value,
             Button::B => self.button_b = value,
@@ -121,13 +457,28 @@ impl ControllerInput {
             Button::DPadDown => self.button_d_pad_down = value,
             Button::DPadLeft => self.button_d_pad_left = value,
             Button::DPadRight => self.button_d_pad_right = value,
-            _ => {}
+            _ => if let Some(slot) = self.other_buttons.get_mut(*button as usize) { *slot = value; },
         }
     }
 }
 
 
 
+fn normalize(value: i16) -> f32 {
+    (value as f32 / i16::MAX as f32).clamp(-1.0, 1.0)
+}
+
+fn to_motor_strength(strength: f32) -> u16 {
+    (strength.clamp(0.0, 1.0) * u16::MAX as f32) as u16
+}
+
+// maps a value from the dead zone up to 1.0 to the range 0.0 to 1.0
+fn rescale(value: f32, dead_zone: f32) -> f32 {
+    if value <= dead_zone { return 0.0; }
+    ((value - dead_zone) / (1.0 - dead_zone).max(f32::EPSILON)).min(1.0)
+}
+
+
 // ControllerAxisMotion {
 //     timestamp: u32,
 //     which: u32,
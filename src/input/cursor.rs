@@ -0,0 +1,110 @@
+use std::path::Path;
+
+use sdl2::{
+    image::LoadSurface,
+    mouse::{ Cursor, MouseUtil, SystemCursor },
+    surface::Surface,
+    video::Window,
+};
+
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct CursorId(usize);
+
+
+// Cursor settings are stored here and handed to SDL at the start of the next frame.
+pub struct CursorControl {
+    relative_mode: bool,
+    visible: bool,
+    confined: bool,
+    warp: Option<(i32, i32)>,
+    cursors: Vec<Cursor>,
+    current: Option<CursorId>,
+    system_cursor: Option<Cursor>,
+    changed: bool,
+}
+
+impl CursorControl {
+    pub(crate) fn new() -> Self {
+        CursorControl {
+            relative_mode: false,
+            visible: true,
+            confined: false,
+            warp: None,
+            cursors: Vec::new(),
+            current: None,
+            system_cursor: None,
+            changed: false,
+        }
+    }
+
+    // hides and locks the cursor, use `MouseInput::relative` to read the mouse movement
+    pub fn set_relative_mode(&mut self, enabled: bool) {
+        self.relative_mode = enabled;
+        self.changed = true;
+    }
+    pub fn relative_mode(&self) -> bool {
+        self.relative_mode
+    }
+
+    pub fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+        self.changed = true;
+    }
+    pub fn visible(&self) -> bool {
+        self.visible
+    }
+
+    // keeps the cursor inside of the window
+    pub fn set_confined(&mut self, confined: bool) {
+        self.confined = confined;
+        self.changed = true;
+    }
+    pub fn confined(&self) -> bool {
+        self.confined
+    }
+
+    // moves the cursor to a position in window coordinates
+    pub fn warp(&mut self, x: i32, y: i32) {
+        self.warp = Some((x, y));
+    }
+
+    // loads a cursor image, the hotspot is the pixel that points at things
+    pub fn load<P: AsRef<Path>>(&mut self, path: P, hotspot_x: i32, hotspot_y: i32) -> Result<CursorId, String> {
+        let surface = Surface::from_file(path)?;
+        let cursor = Cursor::from_surface(surface, hotspot_x, hotspot_y)?;
+        self.cursors.push(cursor);
+        Ok(CursorId(self.cursors.len() - 1))
+    }
+
+    // switches to a loaded cursor, None goes back to the system arrow
+    pub fn set(&mut self, cursor: Option<CursorId>) {
+        self.current = cursor;
+        self.changed = true;
+    }
+    pub fn current(&self) -> Option<CursorId> {
+        self.current
+    }
+
+    pub(crate) fn apply(&mut self, mouse: &MouseUtil, window: &mut Window) {
+        if let Some((x, y)) = self.warp.take() {
+            mouse.warp_mouse_in_window(window, x, y);
+        }
+        if !self.changed { return; }
+        self.changed = false;
+
+        mouse.set_relative_mouse_mode(self.relative_mode);
+        mouse.show_cursor(self.visible);
+        window.set_grab(self.confined);
+
+        match self.current.and_then(|id| self.cursors.get(id.0)) {
+            Some(cursor) => cursor.set(),
+            None => {
+                if self.system_cursor.is_none() {
+                    self.system_cursor = Cursor::from_system(SystemCursor::Arrow).ok();
+                }
+                if let Some(cursor) = &self.system_cursor { cursor.set(); }
+            },
+        }
+    }
+}
@@ -0,0 +1,22 @@
+// Ready made rumble effects, strengths go from 0.0 to 1.0.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum HapticPreset {
+    Tap,
+    Hit,
+    HeavyHit,
+    Explosion,
+    Custom { low_frequency: f32, high_frequency: f32, duration_ms: u32 },
+}
+
+impl HapticPreset {
+    // returns the low and high frequency strength and the duration in milliseconds
+    pub fn rumble(&self) -> (f32, f32, u32) {
+        match *self {
+            HapticPreset::Tap => (0.0, 0.3, 60),
+            HapticPreset::Hit => (0.4, 0.6, 150),
+            HapticPreset::HeavyHit => (0.8, 0.7, 250),
+            HapticPreset::Explosion => (1.0, 0.5, 600),
+            HapticPreset::Custom { low_frequency, high_frequency, duration_ms } => (low_frequency, high_frequency, duration_ms),
+        }
+    }
+}
@@ -1,32 +1,316 @@
 
 
-#[derive(Clone)]
-pub enum Key {
-    Esc = 0, Left = 1, Right = 2, Up = 3, Down = 4,
-
-    ShiftLeft = 5, ShiftRight = 6, CtrlLeft = 7, CtrlRight = 8, AltLeft = 9,
-    AltRight = 10, Delete = 11, Enter = 12, Space = 13,
-
-    Nr1 = 14, Nr2 = 15, Nr3 = 16, Nr4 = 17, Nr5 = 18, Nr6 = 19, Nr7 = 20,
-    Nr8 = 21, Nr9 = 22, Nr0 = 23,
-    
-    Q = 24, W = 25, E = 26, R = 27, T = 28, Y = 29, U = 30, I = 31, O = 32, P = 33,
-    A = 34, S = 35, D = 36, F = 37, G = 38, H = 39, J = 40, K = 41, L = 42, Z = 43,
-    X = 44, C = 45, V = 46, B = 47, N = 48, M = 49,
+use sdl2::keyboard::{ Keycode, Scancode };
+
+
+// scancodes are physical key positions, SDL numbers them below 512
+const SCANCODE_COUNT: usize = 512;
+
+
+// Defines `Key` with a variant for every SDL keycode, plus `Key::Unknown` for
+// key events SDL could not name.
+macro_rules! keys {
+    ($($key:ident => $keycode:ident,)*) => {
+        #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+        pub enum Key {
+            $($key,)*
+            Unknown,
+        }
+
+        impl Key {
+            pub const COUNT: usize = [$(Key::$key,)* Key::Unknown].len();
+
+            pub fn from_keycode(keycode: Keycode) -> Key {
+                match keycode {
+                    $(Keycode::$keycode => Key::$key,)*
+                }
+            }
+
+            pub fn keycode(self) -> Option<Keycode> {
+                match self {
+                    $(Key::$key => Some(Keycode::$keycode),)*
+                    Key::Unknown => None,
+                }
+            }
+
+            pub fn name(self) -> &'static str {
+                match self {
+                    $(Key::$key => stringify!($key),)*
+                    Key::Unknown => "Unknown",
+                }
+            }
+
+            pub fn from_name(name: &str) -> Option<Key> {
+                match name {
+                    $(stringify!($key) => Some(Key::$key),)*
+                    "Unknown" => Some(Key::Unknown),
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+keys! {
+    Esc => Escape,
+    Left => Left,
+    Right => Right,
+    Up => Up,
+    Down => Down,
+    ShiftLeft => LShift,
+    ShiftRight => RShift,
+    CtrlLeft => LCtrl,
+    CtrlRight => RCtrl,
+    AltLeft => LAlt,
+    AltRight => RAlt,
+    Delete => Delete,
+    Enter => Return,
+    Space => Space,
+    Nr1 => Num1,
+    Nr2 => Num2,
+    Nr3 => Num3,
+    Nr4 => Num4,
+    Nr5 => Num5,
+    Nr6 => Num6,
+    Nr7 => Num7,
+    Nr8 => Num8,
+    Nr9 => Num9,
+    Nr0 => Num0,
+    Q => Q,
+    W => W,
+    E => E,
+    R => R,
+    T => T,
+    Y => Y,
+    U => U,
+    I => I,
+    O => O,
+    P => P,
+    A => A,
+    S => S,
+    D => D,
+    F => F,
+    G => G,
+    H => H,
+    J => J,
+    K => K,
+    L => L,
+    Z => Z,
+    X => X,
+    C => C,
+    V => V,
+    B => B,
+    N => N,
+    M => M,
+
+    // all other keys, in SDL keycode order
+    Backspace => Backspace,
+    Tab => Tab,
+    Exclaim => Exclaim,
+    Quotedbl => Quotedbl,
+    Hash => Hash,
+    Dollar => Dollar,
+    Percent => Percent,
+    Ampersand => Ampersand,
+    Quote => Quote,
+    LeftParen => LeftParen,
+    RightParen => RightParen,
+    Asterisk => Asterisk,
+    Plus => Plus,
+    Comma => Comma,
+    Minus => Minus,
+    Period => Period,
+    Slash => Slash,
+    Colon => Colon,
+    Semicolon => Semicolon,
+    Less => Less,
+    Equals => Equals,
+    Greater => Greater,
+    Question => Question,
+    At => At,
+    LeftBracket => LeftBracket,
+    Backslash => Backslash,
+    RightBracket => RightBracket,
+    Caret => Caret,
+    Underscore => Underscore,
+    Backquote => Backquote,
+    CapsLock => CapsLock,
+    F1 => F1,
+    F2 => F2,
+    F3 => F3,
+    F4 => F4,
+    F5 => F5,
+    F6 => F6,
+    F7 => F7,
+    F8 => F8,
+    F9 => F9,
+    F10 => F10,
+    F11 => F11,
+    F12 => F12,
+    PrintScreen => PrintScreen,
+    ScrollLock => ScrollLock,
+    Pause => Pause,
+    Insert => Insert,
+    Home => Home,
+    PageUp => PageUp,
+    End => End,
+    PageDown => PageDown,
+    NumLockClear => NumLockClear,
+    KpDivide => KpDivide,
+    KpMultiply => KpMultiply,
+    KpMinus => KpMinus,
+    KpPlus => KpPlus,
+    KpEnter => KpEnter,
+    Kp1 => Kp1,
+    Kp2 => Kp2,
+    Kp3 => Kp3,
+    Kp4 => Kp4,
+    Kp5 => Kp5,
+    Kp6 => Kp6,
+    Kp7 => Kp7,
+    Kp8 => Kp8,
+    Kp9 => Kp9,
+    Kp0 => Kp0,
+    KpPeriod => KpPeriod,
+    Application => Application,
+    Power => Power,
+    KpEquals => KpEquals,
+    F13 => F13,
+    F14 => F14,
+    F15 => F15,
+    F16 => F16,
+    F17 => F17,
+    F18 => F18,
+    F19 => F19,
+    F20 => F20,
+    F21 => F21,
+    F22 => F22,
+    F23 => F23,
+    F24 => F24,
+    Execute => Execute,
+    Help => Help,
+    Menu => Menu,
+    Select => Select,
+    Stop => Stop,
+    Again => Again,
+    Undo => Undo,
+    Cut => Cut,
+    Copy => Copy,
+    Paste => Paste,
+    Find => Find,
+    Mute => Mute,
+    VolumeUp => VolumeUp,
+    VolumeDown => VolumeDown,
+    KpComma => KpComma,
+    KpEqualsAS400 => KpEqualsAS400,
+    AltErase => AltErase,
+    Sysreq => Sysreq,
+    Cancel => Cancel,
+    Clear => Clear,
+    Prior => Prior,
+    Return2 => Return2,
+    Separator => Separator,
+    Out => Out,
+    Oper => Oper,
+    ClearAgain => ClearAgain,
+    CrSel => CrSel,
+    ExSel => ExSel,
+    Kp00 => Kp00,
+    Kp000 => Kp000,
+    ThousandsSeparator => ThousandsSeparator,
+    DecimalSeparator => DecimalSeparator,
+    CurrencyUnit => CurrencyUnit,
+    CurrencySubUnit => CurrencySubUnit,
+    KpLeftParen => KpLeftParen,
+    KpRightParen => KpRightParen,
+    KpLeftBrace => KpLeftBrace,
+    KpRightBrace => KpRightBrace,
+    KpTab => KpTab,
+    KpBackspace => KpBackspace,
+    KpA => KpA,
+    KpB => KpB,
+    KpC => KpC,
+    KpD => KpD,
+    KpE => KpE,
+    KpF => KpF,
+    KpXor => KpXor,
+    KpPower => KpPower,
+    KpPercent => KpPercent,
+    KpLess => KpLess,
+    KpGreater => KpGreater,
+    KpAmpersand => KpAmpersand,
+    KpDblAmpersand => KpDblAmpersand,
+    KpVerticalBar => KpVerticalBar,
+    KpDblVerticalBar => KpDblVerticalBar,
+    KpColon => KpColon,
+    KpHash => KpHash,
+    KpSpace => KpSpace,
+    KpAt => KpAt,
+    KpExclam => KpExclam,
+    KpMemStore => KpMemStore,
+    KpMemRecall => KpMemRecall,
+    KpMemClear => KpMemClear,
+    KpMemAdd => KpMemAdd,
+    KpMemSubtract => KpMemSubtract,
+    KpMemMultiply => KpMemMultiply,
+    KpMemDivide => KpMemDivide,
+    KpPlusMinus => KpPlusMinus,
+    KpClear => KpClear,
+    KpClearEntry => KpClearEntry,
+    KpBinary => KpBinary,
+    KpOctal => KpOctal,
+    KpDecimal => KpDecimal,
+    KpHexadecimal => KpHexadecimal,
+    GuiLeft => LGui,
+    GuiRight => RGui,
+    Mode => Mode,
+    AudioNext => AudioNext,
+    AudioPrev => AudioPrev,
+    AudioStop => AudioStop,
+    AudioPlay => AudioPlay,
+    AudioMute => AudioMute,
+    MediaSelect => MediaSelect,
+    Www => Www,
+    Mail => Mail,
+    Calculator => Calculator,
+    Computer => Computer,
+    AcSearch => AcSearch,
+    AcHome => AcHome,
+    AcBack => AcBack,
+    AcForward => AcForward,
+    AcStop => AcStop,
+    AcRefresh => AcRefresh,
+    AcBookmarks => AcBookmarks,
+    BrightnessDown => BrightnessDown,
+    BrightnessUp => BrightnessUp,
+    DisplaySwitch => DisplaySwitch,
+    KbdIllumToggle => KbdIllumToggle,
+    KbdIllumDown => KbdIllumDown,
+    KbdIllumUp => KbdIllumUp,
+    Eject => Eject,
+    Sleep => Sleep,
 }
 
 pub struct KeyboardInput {
     pub states: Vec<bool>,
     pub changed: Vec<bool>,
     recent_activity: Vec<usize>,
+
+    scancode_states: Vec<bool>,
+    scancode_changed: Vec<bool>,
+    recent_scancodes: Vec<usize>,
 }
 
 impl KeyboardInput {
     pub fn new() -> Self {
         KeyboardInput {
-            states: vec![false; 50],
-            changed: vec![false; 50],
-            recent_activity: Vec::with_capacity(50),
+            states: vec![false; Key::COUNT],
+            changed: vec![false; Key::COUNT],
+            recent_activity: Vec::with_capacity(Key::COUNT),
+
+            scancode_states: vec![false; SCANCODE_COUNT],
+            scancode_changed: vec![false; SCANCODE_COUNT],
+            recent_scancodes: Vec::with_capacity(SCANCODE_COUNT),
         }
     }
 
@@ -34,14 +318,46 @@ impl KeyboardInput {
         while let Some(activity) = self.recent_activity.pop() {
             self.changed[activity] = false;
         }
+        while let Some(activity) = self.recent_scancodes.pop() {
+            self.scancode_changed[activity] = false;
+        }
+    }
+
+    // releases every held key, used when text input takes over the keyboard
+    pub(crate) fn release_all(&mut self) {
+        for index in 0..Key::COUNT {
+            if self.states[index] {
+                if !self.changed[index] { self.recent_activity.push(index); }
+                self.states[index] = false;
+                self.changed[index] = true;
+            }
+        }
+        for index in 0..SCANCODE_COUNT {
+            if self.scancode_states[index] {
+                if !self.scancode_changed[index] { self.recent_scancodes.push(index); }
+                self.scancode_states[index] = false;
+                self.scancode_changed[index] = true;
+            }
+        }
+    }
+
+    pub(crate) fn set_scancode_state(&mut self, scancode: Scancode, state: bool) {
+        let index = scancode as usize;
+        if index >= SCANCODE_COUNT { return; }
+
+        // keep edges that were not seen by an update yet
+        let changed = self.scancode_states[index] != state;
+        if changed && !self.scancode_changed[index] { self.recent_scancodes.push(index); }
+        self.scancode_changed[index] |= changed;
+        self.scancode_states[index] = state;
     }
 
     pub(crate) fn set_state(&mut self, key: Key, state: bool) {
         let index = key as usize;
-        self.changed[index] = self.states[index] != state;
+        let changed = self.states[index] != state;
+        if changed && !self.changed[index] { self.recent_activity.push(index); }
+        self.changed[index] |= changed;
         self.states[index] = state;
-        
-        if self.changed[index] { self.recent_activity.push(index); }
     }
 
     pub fn pressed(&self, key: Key) -> bool {
@@ -55,5 +371,28 @@ impl KeyboardInput {
         let index = key as usize;
         !self.states[index] && self.changed[index]
     }
-}
 
+    // Scancode queries use the physical key position, so Scancode::W is the key
+    // above Scancode::S on QWERTY, AZERTY and Dvorak keyboards alike.
+    pub fn scancode_pressed(&self, scancode: Scancode) -> bool {
+        let index = scancode as usize;
+        index < SCANCODE_COUNT && self.scancode_states[index] && self.scancode_changed[index]
+    }
+    pub fn scancode_down(&self, scancode: Scancode) -> bool {
+        let index = scancode as usize;
+        index < SCANCODE_COUNT && self.scancode_states[index]
+    }
+    pub fn scancode_released(&self, scancode: Scancode) -> bool {
+        let index = scancode as usize;
+        index < SCANCODE_COUNT && !self.scancode_states[index] && self.scancode_changed[index]
+    }
+
+    // the name of the key at a scancode position for the current keyboard layout, 
+    // use this to show bindings in menus, for example "Z" for Scancode::W on AZERTY
+    pub fn scancode_name(scancode: Scancode) -> String {
+        match Keycode::from_scancode(scancode) {
+            Some(keycode) => keycode.name(),
+            None => String::from(scancode.name()),
+        }
+    }
+}
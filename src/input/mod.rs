@@ -1,16 +1,32 @@
 mod keyboard;
 mod mouse;
 mod controller;
+mod actions;
+mod bindings;
+mod text;
+mod cursor;
+mod haptics;
 
 use sdl2::keyboard::Keycode;
+pub use sdl2::keyboard::Scancode;
 pub use keyboard::{ Key, KeyboardInput };
 pub use mouse::{ MouseInput };
-pub use controller::{ ControllerList };
+pub use controller::{ ControllerList, ControllerInput, ControllerSettings, DeadZoneShape, ResponseCurve };
+pub use actions::{ ActionMap, AxisDirection, Binding };
+pub use bindings::{ BindingConflict, BindingReport };
+pub use text::{ TextInput };
+pub use cursor::{ CursorControl, CursorId };
+pub use haptics::{ HapticPreset };
+pub use sdl2::controller::{ Axis, Button };
+pub use sdl2::mouse::MouseButton;
 
 pub struct Input {
     pub keyboard: KeyboardInput,
     pub mouse: MouseInput,
     pub controllers: ControllerList,
+    pub actions: ActionMap,
+    pub text: TextInput,
+    pub cursor: CursorControl,
 }
 
 impl Input {
@@ -19,64 +35,39 @@ impl Input {
             keyboard: KeyboardInput::new(),
             mouse: MouseInput::new(),
             controllers: ControllerList::new(),
+            actions: ActionMap::new(),
+            text: TextInput::new(),
+            cursor: CursorControl::new(),
         }
     }
-}
-
-pub(crate) fn map_keys(input: &mut KeyboardInput, keycode: Keycode, state: bool) {
-    match keycode {
-        Keycode::Right => input.set_state(Key::Right, state),
-        Keycode::Left => input.set_state(Key::Left, state),
-        Keycode::Down => input.set_state(Key::Down, state),
-        Keycode::Up => input.set_state(Key::Up, state),
 
-        Keycode::LShift => input.set_state(Key::ShiftLeft, state),
-        Keycode::RShift => input.set_state(Key::ShiftRight, state),
-        Keycode::LCtrl => input.set_state(Key::CtrlLeft, state),
-        Keycode::RCtrl => input.set_state(Key::CtrlRight, state),
-        Keycode::LAlt => input.set_state(Key::AltLeft, state),
-        Keycode::RAlt => input.set_state(Key::AltRight, state),
-        Keycode::Escape => input.set_state(Key::Esc, state),
-        Keycode::Return => input.set_state(Key::Enter, state),
-        Keycode::Space => input.set_state(Key::Space, state),
+    // Clears pressed/released edges, wheel and relative motion and typed text. This
+    // runs after an update has seen them, frames without an update keep them around.
+    pub(crate) fn releave_activity(&mut self) {
+        self.keyboard.releave_activity();
+        self.mouse.releave_activity();
+        self.text.releave_activity();
+        self.controllers.releave_activity();
+        self.actions.releave_activity();
+    }
 
-        Keycode::Num0 => input.set_state(Key::Nr0, state),
-        Keycode::Num1 => input.set_state(Key::Nr1, state),
-        Keycode::Num2 => input.set_state(Key::Nr2, state),
-        Keycode::Num3 => input.set_state(Key::Nr3, state),
-        Keycode::Num4 => input.set_state(Key::Nr4, state),
-        Keycode::Num5 => input.set_state(Key::Nr5, state),
-        Keycode::Num6 => input.set_state(Key::Nr6, state),
-        Keycode::Num7 => input.set_state(Key::Nr7, state),
-        Keycode::Num8 => input.set_state(Key::Nr8, state),
-        Keycode::Num9 => input.set_state(Key::Nr9, state),
+    // recalculates action values from the input state of this frame, all actions
+    // are released while text input is active
+    pub(crate) fn update_actions(&mut self) {
+        if self.text.active() {
+            self.actions.release_all();
+        } else {
+            self.actions.update(&self.keyboard, &self.mouse, &self.controllers);
+        }
+    }
+}
 
-        Keycode::Q => input.set_state(Key::Q, state),
-        Keycode::W => input.set_state(Key::W, state),
-        Keycode::E => input.set_state(Key::E, state),
-        Keycode::R => input.set_state(Key::R, state),
-        Keycode::T => input.set_state(Key::T, state),
-        Keycode::Y => input.set_state(Key::Y, state),
-        Keycode::U => input.set_state(Key::U, state),
-        Keycode::I => input.set_state(Key::I, state),
-        Keycode::O => input.set_state(Key::O, state),
-        Keycode::P => input.set_state(Key::P, state),
-        Keycode::A => input.set_state(Key::A, state),
-        Keycode::S => input.set_state(Key::S, state),
-        Keycode::D => input.set_state(Key::D, state),
-        Keycode::F => input.set_state(Key::F, state),
-        Keycode::G => input.set_state(Key::G, state),
-        Keycode::H => input.set_state(Key::H, state),
-        Keycode::J => input.set_state(Key::J, state),
-        Keycode::K => input.set_state(Key::K, state),
-        Keycode::L => input.set_state(Key::L, state),
-        Keycode::Z => input.set_state(Key::Z, state),
-        Keycode::X => input.set_state(Key::X, state),
-        Keycode::C => input.set_state(Key::C, state),
-        Keycode::V => input.set_state(Key::V, state),
-        Keycode::B => input.set_state(Key::B, state),
-        Keycode::N => input.set_state(Key::N, state),
-        Keycode::M => input.set_state(Key::M, state),
-        _ => {},
+pub(crate) fn map_keys(input: &mut KeyboardInput, keycode: Option<Keycode>, scancode: Option<Scancode>, state: bool) {
+    match keycode {
+        Some(keycode) => input.set_state(Key::from_keycode(keycode), state),
+        None => input.set_state(Key::Unknown, state),
     }
-}
\ No newline at end of file
+    if let Some(scancode) = scancode {
+        input.set_scancode_state(scancode, state);
+    }
+}
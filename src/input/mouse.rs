@@ -1,3 +1,6 @@
+use sdl2::mouse::{ MouseButton, MouseWheelDirection };
+
+const BUTTON_COUNT: usize = 5;
 
 pub struct MouseInput {
     pub x: i32,
@@ -5,7 +8,15 @@ pub struct MouseInput {
     pub left_button: bool,
     pub middle_button: bool,
     pub right_button: bool,
+    pub x1_button: bool,
+    pub x2_button: bool,
+    // wheel movement during the last frame, positive y scrolls away from the user
     pub wheel: (i32, i32),
+    // mouse movement during the last frame, also works when the cursor is held in place
+    pub relative: (i32, i32),
+
+    changed: [bool; BUTTON_COUNT],
+    double_clicked: [bool; BUTTON_COUNT],
 }
 
 impl MouseInput {
@@ -16,12 +27,92 @@ impl MouseInput {
             left_button: false,
             middle_button: false,
             right_button: false,
+            x1_button: false,
+            x2_button: false,
             wheel: (0, 0),
+            relative: (0, 0),
+
+            changed: [false; BUTTON_COUNT],
+            double_clicked: [false; BUTTON_COUNT],
+        }
+    }
+
+    pub(crate) fn releave_activity(&mut self) {
+        self.wheel = (0, 0);
+        self.relative = (0, 0);
+        self.changed = [false; BUTTON_COUNT];
+        self.double_clicked = [false; BUTTON_COUNT];
+    }
+
+    pub(crate) fn set_position(&mut self, x: i32, y: i32, xrel: i32, yrel: i32) {
+        self.x = x;
+        self.y = y;
+        self.relative.0 += xrel;
+        self.relative.1 += yrel;
+    }
+
+    pub(crate) fn scroll(&mut self, x: i32, y: i32, direction: MouseWheelDirection) {
+        // natural scrolling reports flipped values, undo that so up is always positive
+        let flip = if direction == MouseWheelDirection::Flipped { -1 } else { 1 };
+        self.wheel.0 += x * flip;
+        self.wheel.1 += y * flip;
+    }
+
+    pub(crate) fn set_button(&mut self, button: MouseButton, state: bool, clicks: u8) {
+        let slot = match button {
+            MouseButton::Left => &mut self.left_button,
+            MouseButton::Middle => &mut self.middle_button,
+            MouseButton::Right => &mut self.right_button,
+            MouseButton::X1 => &mut self.x1_button,
+            MouseButton::X2 => &mut self.x2_button,
+            MouseButton::Unknown => return,
+        };
+        let changed = *slot != state;
+        *slot = state;
+
+        let index = button_index(button);
+        self.changed[index] |= changed;
+        if state && clicks == 2 { self.double_clicked[index] = true; }
+    }
+
+    pub fn pressed(&self, button: MouseButton) -> bool {
+        self.down(button) && self.changed(button)
+    }
+    pub fn down(&self, button: MouseButton) -> bool {
+        match button {
+            MouseButton::Left => self.left_button,
+            MouseButton::Middle => self.middle_button,
+            MouseButton::Right => self.right_button,
+            MouseButton::X1 => self.x1_button,
+            MouseButton::X2 => self.x2_button,
+            MouseButton::Unknown => false,
         }
     }
+    pub fn released(&self, button: MouseButton) -> bool {
+        !self.down(button) && self.changed(button)
+    }
+
+    // true for the frame in which a button was pressed for the second time in quick succession
+    pub fn double_clicked(&self, button: MouseButton) -> bool {
+        button != MouseButton::Unknown && self.double_clicked[button_index(button)]
+    }
+
+    fn changed(&self, button: MouseButton) -> bool {
+        button != MouseButton::Unknown && self.changed[button_index(button)]
+    }
 }
 
 
+fn button_index(button: MouseButton) -> usize {
+    match button {
+        MouseButton::Left | MouseButton::Unknown => 0,
+        MouseButton::Middle => 1,
+        MouseButton::Right => 2,
+        MouseButton::X1 => 3,
+        MouseButton::X2 => 4,
+    }
+}
+
 // MouseMotion {
 //     timestamp: u32,
 //     window_id: u32,
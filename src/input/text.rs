@@ -0,0 +1,126 @@
+use sdl2::rect::Rect;
+
+
+// Typed text for name entry and chat. While text input is active key presses go
+// to the text instead of the keyboard state and all actions are released, mouse and
+// controller state can still be read directly.
+pub struct TextInput {
+    pub text: String,
+    // the longest text in characters, longer input is cut off
+    pub max_length: Option<usize>,
+    active: bool,
+    area: Option<Rect>,
+
+    composition: String,
+    composition_cursor: (i32, i32),
+    typed: String,
+    submitted: Option<String>,
+    cancelled: bool,
+}
+
+impl TextInput {
+    pub(crate) fn new() -> Self {
+        TextInput {
+            text: String::new(),
+            max_length: None,
+            active: false,
+            area: None,
+
+            composition: String::new(),
+            composition_cursor: (0, 0),
+            typed: String::new(),
+            submitted: None,
+            cancelled: false,
+        }
+    }
+
+    pub fn start(&mut self) {
+        self.active = true;
+    }
+
+    pub fn stop(&mut self) {
+        self.active = false;
+        self.composition.clear();
+    }
+
+    pub fn active(&self) -> bool {
+        self.active
+    }
+
+    pub fn clear(&mut self) {
+        self.text.clear();
+    }
+
+    // where the text is drawn on screen, input methods show their candidate list near it
+    pub fn set_area(&mut self, area: Rect) {
+        self.area = Some(area);
+    }
+
+    pub(crate) fn take_area(&mut self) -> Option<Rect> {
+        self.area.take()
+    }
+
+    // text that is still being composed by an input method, it is not part of `text` yet
+    pub fn composition(&self) -> &str {
+        &self.composition
+    }
+
+    // start and length of the edited part of the composition, in characters
+    pub fn composition_cursor(&self) -> (i32, i32) {
+        self.composition_cursor
+    }
+
+    // text that was added during the last frame
+    pub fn typed(&self) -> &str {
+        &self.typed
+    }
+
+    // the text, for one frame after enter was pressed
+    pub fn submitted(&self) -> Option<&str> {
+        self.submitted.as_deref()
+    }
+
+    // true for one update after escape was pressed, games usually stop text input then
+    pub fn cancelled(&self) -> bool {
+        self.cancelled
+    }
+
+    pub(crate) fn releave_activity(&mut self) {
+        self.typed.clear();
+        self.submitted = None;
+        self.cancelled = false;
+    }
+
+    pub(crate) fn insert(&mut self, text: &str) {
+        for c in text.chars() {
+            if self.max_length.is_some_and(|max| self.text.chars().count() >= max) { break; }
+            self.text.push(c);
+            self.typed.push(c);
+        }
+        self.composition.clear();
+    }
+
+    pub(crate) fn edit(&mut self, composition: &str, start: i32, length: i32) {
+        self.composition = String::from(composition);
+        self.composition_cursor = (start, length);
+    }
+
+    // backspace only removes text when the input method is not composing
+    pub(crate) fn backspace(&mut self) {
+        if self.composition.is_empty() {
+            self.text.pop();
+        }
+    }
+
+    pub(crate) fn submit(&mut self) {
+        if self.composition.is_empty() {
+            self.submitted = Some(self.text.clone());
+        }
+    }
+
+    pub(crate) fn cancel(&mut self) {
+        if self.composition.is_empty() {
+            self.cancelled = true;
+        }
+    }
+}
@@ -5,12 +5,15 @@ pub mod sprites;
 pub mod timer;
 pub mod transform;
 pub mod camera;
+pub mod lighting;
+pub mod particles;
 
 mod renderer;
 mod scenes;
+mod random;
 
 pub use scenes::Scene;
-pub use renderer::{ Renderer, RenderContext };
+pub use renderer::{ Renderer, RenderContext, GameLoop, Screen };
 pub use swarm::*;
 
 use sprites::Sprite;
@@ -0,0 +1,191 @@
+use sdl2::{
+    pixels::{ Color, PixelFormatEnum },
+    rect::Rect,
+    render::{ BlendMode, Canvas, Texture, TextureCreator },
+    video::{ Window, WindowContext },
+};
+
+use crate::{ camera::Camera, renderer::Screen };
+
+
+const GRADIENT_SIZE: u32 = 128;
+
+
+#[derive(Clone)]
+pub struct PointLight {
+    pub x: f32,
+    pub y: f32,
+    pub radius: f32,
+    pub color: Color,
+    pub intensity: f32,
+    // 1.0 fades linearly towards the edge, higher values make a tighter, brighter core
+    pub falloff: f32,
+}
+
+impl PointLight {
+    pub fn new(x: f32, y: f32, radius: f32) -> Self {
+        PointLight { x, y, radius, color: Color::RGB(255, 255, 255), intensity: 1.0, falloff: 1.0 }
+    }
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+    pub fn with_intensity(mut self, intensity: f32) -> Self {
+        self.intensity = intensity;
+        self
+    }
+    pub fn with_falloff(mut self, falloff: f32) -> Self {
+        self.falloff = falloff;
+        self
+    }
+}
+
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct LightId(usize);
+
+
+// Lights are drawn additively into a light map that starts out at the ambient
+// color, the light map is then multiplied over everything drawn before it.
+pub struct Lighting {
+    pub enabled: bool,
+    pub ambient: Color,
+    lights: Vec<Option<PointLight>>,
+}
+
+impl Lighting {
+    pub fn new() -> Self {
+        Lighting { enabled: false, ambient: Color::RGB(255, 255, 255), lights: Vec::new() }
+    }
+
+    // 0.0 is full daylight and 1.0 is pitch black
+    pub fn set_darkness(&mut self, darkness: f32) {
+        let level = ((1.0 - darkness.clamp(0.0, 1.0)) * 255.0) as u8;
+        self.ambient = Color::RGB(level, level, level);
+    }
+
+    pub fn add(&mut self, light: PointLight) -> LightId {
+        if let Some(index) = self.lights.iter().position(|l| l.is_none()) {
+            self.lights[index] = Some(light);
+            LightId(index)
+        } else {
+            self.lights.push(Some(light));
+            LightId(self.lights.len() - 1)
+        }
+    }
+
+    pub fn remove(&mut self, id: LightId) -> Option<PointLight> {
+        self.lights.get_mut(id.0).and_then(|l| l.take())
+    }
+
+    pub fn get(&self, id: LightId) -> Option<&PointLight> {
+        self.lights.get(id.0).and_then(|l| l.as_ref())
+    }
+
+    pub fn get_mut(&mut self, id: LightId) -> Option<&mut PointLight> {
+        self.lights.get_mut(id.0).and_then(|l| l.as_mut())
+    }
+
+    pub fn clear(&mut self) {
+        self.lights.clear();
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &PointLight> {
+        self.lights.iter().filter_map(|l| l.as_ref())
+    }
+}
+
+impl Default for Lighting {
+    fn default() -> Self { Lighting::new() }
+}
+
+
+// render target and light gradients, one gradient is generated per falloff value
+pub(crate) struct LightMap<'c> {
+    target: Texture<'c>,
+    gradients: Vec<(u32, Texture<'c>)>,
+}
+
+impl<'c> LightMap<'c> {
+    pub(crate) fn new(creator: &'c TextureCreator<WindowContext>, screen: &Screen) -> Result<Self, String> {
+        let mut target = creator
+            .create_texture_target(None, screen.width, screen.height)
+            .map_err(|e| e.to_string())?;
+        target.set_blend_mode(BlendMode::Mod);
+
+        Ok(LightMap { target, gradients: Vec::new() })
+    }
+
+    pub(crate) fn render(
+        &mut self,
+        creator: &'c TextureCreator<WindowContext>,
+        canvas: &mut Canvas<Window>,
+        lighting: &Lighting,
+        camera: &Camera,
+        screen: &Screen,
+    ) -> Result<(), String> {
+        for light in lighting.iter() {
+            let key = falloff_key(light.falloff);
+            if !self.gradients.iter().any(|g| g.0 == key) {
+                self.gradients.push((key, create_gradient(creator, light.falloff)?));
+            }
+        }
+
+        let gradients = &mut self.gradients;
+        let draw_color = canvas.draw_color();
+
+        let mut result = Ok(());
+        canvas.with_texture_canvas(&mut self.target, |light_canvas| {
+            light_canvas.set_draw_color(lighting.ambient);
+            light_canvas.clear();
+
+            for light in lighting.iter() {
+                let key = falloff_key(light.falloff);
+                let gradient = &mut gradients.iter_mut().find(|g| g.0 == key).unwrap().1;
+                let radius = light.radius * camera.scale();
+                let (x, y) = camera.world_to_screen(screen, light.x, light.y);
+
+                gradient.set_color_mod(light.color.r, light.color.g, light.color.b);
+                gradient.set_alpha_mod((light.intensity.clamp(0.0, 1.0) * 255.0) as u8);
+                result = light_canvas.copy(
+                    gradient,
+                    None,
+                    Rect::new((x - radius) as i32, (y - radius) as i32, (radius * 2.0) as u32, (radius * 2.0) as u32),
+                );
+                if result.is_err() { break; }
+            }
+        }).map_err(|e| e.to_string())?;
+        result?;
+
+        canvas.set_draw_color(draw_color);
+        canvas.copy(&self.target, None, None)
+    }
+}
+
+
+fn falloff_key(falloff: f32) -> u32 {
+    (falloff.max(0.0) * 100.0) as u32
+}
+
+fn create_gradient(creator: &TextureCreator<WindowContext>, falloff: f32) -> Result<Texture<'_>, String> {
+    let mut gradient = creator
+        .create_texture_static(PixelFormatEnum::RGB24, GRADIENT_SIZE, GRADIENT_SIZE)
+        .map_err(|e| e.to_string())?;
+
+    let half = GRADIENT_SIZE as f32 * 0.5;
+    let mut pixels = Vec::with_capacity((GRADIENT_SIZE * GRADIENT_SIZE * 3) as usize);
+
+    for y in 0..GRADIENT_SIZE {
+        for x in 0..GRADIENT_SIZE {
+            let dx = (x as f32 + 0.5 - half) / half;
+            let dy = (y as f32 + 0.5 - half) / half;
+            let distance = (dx * dx + dy * dy).sqrt().min(1.0);
+            let value = ((1.0 - distance).powf(falloff) * 255.0) as u8;
+            pixels.extend_from_slice(&[value, value, value]);
+        }
+    }
+
+    gradient.update(None, &pixels, (GRADIENT_SIZE * 3) as usize).map_err(|e| e.to_string())?;
+    gradient.set_blend_mode(BlendMode::Add);
+    Ok(gradient)
+}
@@ -0,0 +1,404 @@
+use std::ops::RangeInclusive;
+
+use sdl2::pixels::Color;
+
+use crate::random::Random;
+use crate::sprites::TileSize;
+use crate::swarm::Spawn;
+
+
+#[derive(Clone)]
+pub enum EmitterAnchor {
+    World { x: f32, y: f32 },
+    Entity { spawn: Spawn, offset_x: f32, offset_y: f32 },
+}
+
+impl Default for EmitterAnchor {
+    fn default() -> Self { EmitterAnchor::World { x: 0.0, y: 0.0 } }
+}
+
+
+// emits `count` particles once the emitter reaches the age of `time` seconds
+#[derive(Clone)]
+pub struct Burst { pub time: f32, pub count: u32 }
+
+
+#[derive(Clone)]
+struct Particle {
+    x: f32,
+    y: f32,
+    velocity_x: f32,
+    velocity_y: f32,
+    acceleration_x: f32,
+    acceleration_y: f32,
+    age: f32,
+    lifetime: f32,
+    tile: u16,
+}
+
+
+pub struct EmitterBuilder(Emitter);
+
+impl EmitterBuilder {
+    pub fn new(texure_id: usize) -> Self {
+        EmitterBuilder(Emitter {
+            texure_id,
+
+            tile_size: TileSize::default(),
+            num_tile_cols: 4,
+            tiles: 0..=0,
+            width: 8,
+            height: 8,
+            layer: 0,
+
+            anchor: EmitterAnchor::default(),
+            active: true,
+            spawn_rate: 10.0,
+            bursts: Vec::new(),
+            max_particles: 1_000,
+
+            lifetime: 1.0..=1.0,
+            velocity_x: 0.0..=0.0,
+            velocity_y: 0.0..=0.0,
+            acceleration_x: 0.0..=0.0,
+            acceleration_y: 0.0..=0.0,
+
+            color: (Color::RGB(255, 255, 255), Color::RGB(255, 255, 255)),
+            alpha: (255, 255),
+            scale: (1.0, 1.0),
+            additive: false,
+
+            particles: Vec::new(),
+            position: (0.0, 0.0),
+            age: 0.0,
+            spawn_debt: 0.0,
+            next_burst: 0,
+            random: Random::new(1),
+            seeded: false,
+        })
+    }
+    pub fn with_tile_size(mut self, width: u32, height: u32) -> Self {
+        self.0.tile_size = TileSize { width, height };
+        self
+    }
+    pub fn with_column_count(mut self, num_tile_cols: u16) -> Self {
+        self.0.num_tile_cols = num_tile_cols;
+        self
+    }
+    // each particle picks a random tile from this range
+    pub fn with_tiles(mut self, tiles: RangeInclusive<u16>) -> Self {
+        self.0.tiles = tiles;
+        self
+    }
+    pub fn with_size(mut self, width: u32, height: u32) -> Self {
+        self.0.width = width;
+        self.0.height = height;
+        self
+    }
+    pub fn with_layer(mut self, layer: u8) -> Self {
+        self.0.layer = layer;
+        self
+    }
+    pub fn with_position(mut self, x: f32, y: f32) -> Self {
+        self.0.anchor = EmitterAnchor::World { x, y };
+        self
+    }
+    pub fn with_entity(mut self, spawn: Spawn, offset_x: f32, offset_y: f32) -> Self {
+        self.0.anchor = EmitterAnchor::Entity { spawn, offset_x, offset_y };
+        self
+    }
+    // particles per second, use 0.0 for an emitter that only bursts
+    pub fn with_spawn_rate(mut self, particles_per_second: f32) -> Self {
+        self.0.spawn_rate = particles_per_second;
+        self
+    }
+    pub fn with_burst(mut self, time: f32, count: u32) -> Self {
+        self.0.bursts.push(Burst { time, count });
+        self.0.bursts.sort_by(|a, b| a.time.total_cmp(&b.time));
+        self
+    }
+    pub fn with_max_particles(mut self, max_particles: usize) -> Self {
+        self.0.max_particles = max_particles;
+        self
+    }
+    pub fn with_lifetime(mut self, seconds: RangeInclusive<f32>) -> Self {
+        self.0.lifetime = seconds;
+        self
+    }
+    pub fn with_velocity(mut self, x: RangeInclusive<f32>, y: RangeInclusive<f32>) -> Self {
+        self.0.velocity_x = x;
+        self.0.velocity_y = y;
+        self
+    }
+    pub fn with_acceleration(mut self, x: RangeInclusive<f32>, y: RangeInclusive<f32>) -> Self {
+        self.0.acceleration_x = x;
+        self.0.acceleration_y = y;
+        self
+    }
+    pub fn with_color(mut self, start: Color, end: Color) -> Self {
+        self.0.color = (start, end);
+        self
+    }
+    pub fn with_alpha(mut self, start: u8, end: u8) -> Self {
+        self.0.alpha = (start, end);
+        self
+    }
+    pub fn with_scale(mut self, start: f32, end: f32) -> Self {
+        self.0.scale = (start, end);
+        self
+    }
+    pub fn with_additive_blending(mut self) -> Self {
+        self.0.additive = true;
+        self
+    }
+    // emitters without a seed get their own when they are added to the particle system
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.0.random = Random::new(seed);
+        self.0.seeded = true;
+        self
+    }
+    pub fn build(mut self) -> Emitter {
+        self.0.follow_world_anchor();
+        self.0
+    }
+}
+
+
+// Particles are simulated inside of their emitter instead of the entity swarm,
+// an emitter only holds the few values a particle needs to move and fade.
+pub struct Emitter {
+    pub texure_id: usize,
+
+    pub tile_size: TileSize,
+    pub num_tile_cols: u16,
+    pub tiles: RangeInclusive<u16>,
+    pub width: u32,
+    pub height: u32,
+    pub layer: u8,
+
+    pub anchor: EmitterAnchor,
+    pub active: bool,
+    pub spawn_rate: f32,
+    pub bursts: Vec<Burst>,
+    pub max_particles: usize,
+
+    pub lifetime: RangeInclusive<f32>,
+    pub velocity_x: RangeInclusive<f32>,
+    pub velocity_y: RangeInclusive<f32>,
+    pub acceleration_x: RangeInclusive<f32>,
+    pub acceleration_y: RangeInclusive<f32>,
+
+    pub color: (Color, Color),
+    pub alpha: (u8, u8),
+    pub scale: (f32, f32),
+    pub additive: bool,
+
+    particles: Vec<Particle>,
+    position: (f32, f32),
+    age: f32,
+    spawn_debt: f32,
+    next_burst: usize,
+    random: Random,
+    seeded: bool,
+}
+
+impl Emitter {
+    pub fn count(&self) -> usize {
+        self.particles.len()
+    }
+
+    pub fn position(&self) -> (f32, f32) {
+        self.position
+    }
+
+    // spawns a number of particles right away, emitters attached to an entity use
+    // the entity position of the last update
+    pub fn burst(&mut self, count: u32) {
+        self.follow_world_anchor();
+        for _ in 0..count { self.emit(); }
+    }
+
+    // restarts the emitter, removing all living particles
+    pub fn reset(&mut self) {
+        self.particles.clear();
+        self.age = 0.0;
+        self.spawn_debt = 0.0;
+        self.next_burst = 0;
+    }
+
+    // an emitter is finished when it is not going to spawn anything anymore
+    // and all of its particles have died
+    pub fn finished(&self) -> bool {
+        self.particles.is_empty() &&
+            (!self.active || (self.spawn_rate <= 0.0 && self.next_burst >= self.bursts.len()))
+    }
+
+    fn follow_world_anchor(&mut self) {
+        if let EmitterAnchor::World { x, y } = self.anchor {
+            self.position = (x, y);
+        }
+    }
+
+    fn emit(&mut self) {
+        if self.particles.len() >= self.max_particles { return; }
+
+        let random = &mut self.random;
+        self.particles.push(Particle {
+            x: self.position.0,
+            y: self.position.1,
+            velocity_x: random.range(&self.velocity_x),
+            velocity_y: random.range(&self.velocity_y),
+            acceleration_x: random.range(&self.acceleration_x),
+            acceleration_y: random.range(&self.acceleration_y),
+            age: 0.0,
+            lifetime: random.range(&self.lifetime).max(f32::EPSILON),
+            tile: random.range_u16(&self.tiles),
+        });
+    }
+
+    pub(crate) fn update(&mut self, delta_time: f32) {
+        // simulate living particles and drop the ones that expired
+        let mut i = 0;
+        while i < self.particles.len() {
+            let p = &mut self.particles[i];
+            p.age += delta_time;
+
+            if p.age >= p.lifetime {
+                self.particles.swap_remove(i);
+                continue;
+            }
+            p.velocity_x += p.acceleration_x * delta_time;
+            p.velocity_y += p.acceleration_y * delta_time;
+            p.x += p.velocity_x * delta_time;
+            p.y += p.velocity_y * delta_time;
+            i += 1;
+        }
+
+        if !self.active { return; }
+        self.age += delta_time;
+
+        // spawn new particles
+        while self.next_burst < self.bursts.len() && self.bursts[self.next_burst].time <= self.age {
+            let count = self.bursts[self.next_burst].count;
+            self.burst(count);
+            self.next_burst += 1;
+        }
+
+        if self.spawn_rate > 0.0 {
+            self.spawn_debt += self.spawn_rate * delta_time;
+            while self.spawn_debt >= 1.0 {
+                self.emit();
+                self.spawn_debt -= 1.0;
+            }
+        }
+    }
+
+    // returns the render state of all living particles: position, size, tile and color
+    pub(crate) fn for_each_particle<F>(&self, mut handler: F)
+    where F: FnMut(f32, f32, f32, u16, Color) {
+        for p in &self.particles {
+            let t = p.age / p.lifetime;
+            let scale = lerp(self.scale.0, self.scale.1, t);
+            let (c0, c1) = (self.color.0, self.color.1);
+
+            handler(p.x, p.y, scale, p.tile, Color::RGBA(
+                lerp(c0.r as f32, c1.r as f32, t) as u8,
+                lerp(c0.g as f32, c1.g as f32, t) as u8,
+                lerp(c0.b as f32, c1.b as f32, t) as u8,
+                lerp(self.alpha.0 as f32, self.alpha.1 as f32, t) as u8,
+            ));
+        }
+    }
+
+    pub(crate) fn tile_position(&self, tile: u16) -> (i32, i32) {
+        let col = tile % self.num_tile_cols.max(1);
+        let row = tile / self.num_tile_cols.max(1);
+        ((col as u32 * self.tile_size.width) as i32, (row as u32 * self.tile_size.height) as i32)
+    }
+}
+
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct EmitterId(usize);
+
+
+#[derive(Default)]
+pub struct ParticleSystem {
+    emitters: Vec<Option<Emitter>>,
+    next_seed: u64,
+}
+
+impl ParticleSystem {
+    pub fn new() -> Self {
+        ParticleSystem { emitters: Vec::new(), next_seed: 0 }
+    }
+
+    pub fn add(&mut self, mut emitter: Emitter) -> EmitterId {
+        if !emitter.seeded {
+            self.next_seed += 1;
+            emitter.random = Random::new(self.next_seed);
+            emitter.seeded = true;
+        }
+
+        if let Some(index) = self.emitters.iter().position(|e| e.is_none()) {
+            self.emitters[index] = Some(emitter);
+            EmitterId(index)
+        } else {
+            self.emitters.push(Some(emitter));
+            EmitterId(self.emitters.len() - 1)
+        }
+    }
+
+    pub fn remove(&mut self, id: EmitterId) -> Option<Emitter> {
+        self.emitters.get_mut(id.0).and_then(|e| e.take())
+    }
+
+    pub fn get(&self, id: EmitterId) -> Option<&Emitter> {
+        self.emitters.get(id.0).and_then(|e| e.as_ref())
+    }
+
+    pub fn get_mut(&mut self, id: EmitterId) -> Option<&mut Emitter> {
+        self.emitters.get_mut(id.0).and_then(|e| e.as_mut())
+    }
+
+    // removes all emitters that finished emitting and have no particles left
+    pub fn remove_finished(&mut self) {
+        for slot in self.emitters.iter_mut() {
+            if slot.as_ref().is_some_and(|e| e.finished()) { *slot = None; }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.emitters.clear();
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Emitter> {
+        self.emitters.iter().filter_map(|e| e.as_ref())
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Emitter> {
+        self.emitters.iter_mut().filter_map(|e| e.as_mut())
+    }
+
+    // `locate` returns the position of an entity, or None if it is no longer alive
+    pub(crate) fn update<F>(&mut self, delta_time: f32, locate: F)
+    where F: Fn(&Spawn) -> Option<(f32, f32)> {
+        for emitter in self.iter_mut() {
+            match &emitter.anchor {
+                EmitterAnchor::World { x, y } => emitter.position = (*x, *y),
+                EmitterAnchor::Entity { spawn, offset_x, offset_y } => {
+                    match locate(spawn) {
+                        Some((x, y)) => emitter.position = (x + offset_x, y + offset_y),
+                        None => emitter.active = false,
+                    }
+                },
+            }
+            emitter.update(delta_time);
+        }
+    }
+}
+
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
@@ -0,0 +1,44 @@
+use std::ops::RangeInclusive;
+
+// small xorshift generator, good enough for visual effects and
+// deterministic for a given seed
+#[derive(Clone)]
+pub(crate) struct Random(u64);
+
+impl Random {
+    pub(crate) fn new(seed: u64) -> Self {
+        Random(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1)
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    // returns a value in the range 0.0..1.0
+    pub(crate) fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    pub(crate) fn range(&mut self, range: &RangeInclusive<f32>) -> f32 {
+        range.start() + (range.end() - range.start()) * self.next_f32()
+    }
+
+    pub(crate) fn range_u16(&mut self, range: &RangeInclusive<u16>) -> u16 {
+        let span = (*range.end() as u64).saturating_sub(*range.start() as u64) + 1;
+        range.start() + (self.next_u64() % span) as u16
+    }
+}
+
+// smooth value noise in the range -1.0..1.0, every `seed` gives a different curve
+pub(crate) fn noise(seed: u64, t: f32) -> f32 {
+    let cell = t.floor();
+    let f = t - cell;
+    let cell = cell as i64 as u64;
+    let a = Random::new(seed ^ cell.wrapping_mul(0x5851_F42D)).next_f32() * 2.0 - 1.0;
+    let b = Random::new(seed ^ cell.wrapping_add(1).wrapping_mul(0x5851_F42D)).next_f32() * 2.0 - 1.0;
+    let f = f * f * (3.0 - 2.0 * f);
+    a + (b - a) * f
+}
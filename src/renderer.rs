@@ -1,17 +1,24 @@
 use sdl2::image::{InitFlag, LoadTexture};
-use std::{ cell::RefCell, rc::Rc, };
+use std::{ cell::RefCell, rc::Rc, time::Instant };
 
 use sdl2::{
-    render::{ Canvas, Texture }, 
-    video::{ Window },
+    render::{ Canvas, Texture, TextureCreator, BlendMode }, 
+    video::{ Window, WindowContext },
     event::Event as SdlEvent, 
+    keyboard::{ Keycode, TextInputUtil },
+    mouse::MouseUtil,
+    GameControllerSubsystem, JoystickSubsystem,
+    rect::Rect,
+    EventPump,
 };
-use swarm::Swarm;
+use swarm::{ Swarm, Spawn };
 
 use crate::{
     Entity, Scene, camera::Camera, 
     input::{ self, Input }, 
-    timer::UpdateTimer
+    lighting::{ Lighting, LightMap },
+    particles::ParticleSystem,
+    timer::{ UpdateTimer, Scheduler, StatsOverlay }
 };
 
 #[derive(Clone)]
@@ -26,16 +33,108 @@ pub struct RenderContext<'c, GameData> {
     textures: Vec<Texture<'c>>,
     canvas: Rc<RefCell<Canvas<Window>>>,
     pub timer: UpdateTimer,
+    pub scheduler: Scheduler,
     pub data: GameData,
     pub input: Input,
-    pub camera: Camera,
+    // cameras are drawn in order, the first camera covers the whole window by default
+    // and there is always at least one
+    cameras: Vec<Camera>,
     pub screen : Screen,
+    pub particles: ParticleSystem,
+    pub lighting: Lighting,
+    active_camera: usize,
+    // (x, y, rotation) of every entity before the last fixed update by spawn id, pool
+    // positions change when entities are killed
+    previous_transforms: Vec<Option<(f32, f32, f64)>>,
+    // interpolated (x, y, rotation) by pool position for the frame being drawn
+    drawn_transforms: Vec<(f32, f32, f64)>,
+}
+
+impl<'c, GameData> RenderContext<'c, GameData> {
+    pub fn camera(&self) -> &Camera {
+        &self.cameras[0]
+    }
+
+    pub fn camera_mut(&mut self) -> &mut Camera {
+        &mut self.cameras[0]
+    }
+
+    pub fn add_camera(&mut self, camera: Camera) -> usize {
+        self.cameras.push(camera);
+        self.cameras.len() - 1
+    }
+
+    // the first camera can not be removed, other cameras move down one index
+    pub fn remove_camera(&mut self, index: usize) -> Option<Camera> {
+        if index == 0 || index >= self.cameras.len() { return None; }
+        Some(self.cameras.remove(index))
+    }
+
+    pub fn get_camera(&self, index: usize) -> Option<&Camera> {
+        self.cameras.get(index)
+    }
+
+    pub fn get_camera_mut(&mut self, index: usize) -> Option<&mut Camera> {
+        self.cameras.get_mut(index)
+    }
+
+    pub fn cameras(&self) -> &[Camera] {
+        &self.cameras
+    }
+
+    pub fn cameras_mut(&mut self) -> &mut [Camera] {
+        &mut self.cameras
+    }
+
+    // returns the index of the top most camera whose viewport contains a screen position
+    pub fn camera_at(&self, x: i32, y: i32) -> Option<usize> {
+        self.cameras.iter().rposition(|camera| camera.view_rect(&self.screen).contains_point((x, y)))
+    }
+
+    // None until a fixed update ran with the entity spawned, so new entities draw where 
+    // they are placed
+    pub fn previous_transform(&self, spawn: &Spawn) -> Option<(f32, f32, f64)> {
+        self.previous_transforms.get(spawn.id()).copied().flatten()
+    }
+
+    // draws the entity where it is instead of blending from where it was, use this after
+    // moving it a long way or reusing a killed spawn within the same update
+    pub fn reset_interpolation(&mut self, spawn: &Spawn) {
+        if let Some(previous) = self.previous_transforms.get_mut(spawn.id()) {
+            *previous = None;
+        }
+    }
 }
 
 pub struct Renderer {
+    pub title: String,
     pub event_pump: sdl2::EventPump,
     pub canvas: Rc<RefCell<Canvas<Window>>>,
     pub screen : Screen,
+    texture_creator: TextureCreator<WindowContext>,
+    text_input: TextInputUtil,
+    mouse: MouseUtil,
+    controller_subsystem: GameControllerSubsystem,
+    joystick_subsystem: JoystickSubsystem,
+}
+
+type SceneHandler<EntityState, GameData> = fn(&mut Swarm<Entity<EntityState>, RenderContext<GameData>>);
+
+// A running scene, created by `Renderer::start`. Every call to `step` runs exactly one 
+// input, update and render cycle, `finish` tells the scene it has ended.
+pub struct GameLoop<'r, EntityState: Default + Clone, GameData> {
+    pub swarm: Swarm<Entity<EntityState>, RenderContext<'r, GameData>>,
+    title: &'r str,
+    event_pump: &'r mut EventPump,
+    canvas: Rc<RefCell<Canvas<Window>>>,
+    texture_creator: &'r TextureCreator<WindowContext>,
+    text_input: &'r TextInputUtil,
+    mouse: &'r MouseUtil,
+    controller_subsystem: &'r GameControllerSubsystem,
+    joystick_subsystem: &'r JoystickSubsystem,
+    light_map: Option<LightMap<'r>>,
+    on_update: SceneHandler<EntityState, GameData>,
+    on_end: fn(),
 }
 
 impl Renderer {
@@ -55,10 +154,21 @@ impl Renderer {
             .build()
             .map_err(|e| e.to_string())?;
 
+        let texture_creator = canvas.texture_creator();
         let canvas = Rc::new(RefCell::new(canvas));
         let event_pump = sdl_context.event_pump()?;
 
+        // SDL starts with text input enabled, games turn it on when they need it
+        let text_input = video_subsystem.text_input();
+        text_input.stop();
+        let mouse = sdl_context.mouse();
+
+        // controllers that are already connected are announced with device added events
+        let controller_subsystem = sdl_context.game_controller()?;
+        let joystick_subsystem = sdl_context.joystick()?;
+
         let renderer = Renderer {
+            title: String::from(title),
             event_pump,
             canvas,
             screen: Screen { 
@@ -67,29 +177,53 @@ impl Renderer {
                 center_x: width as i32 / 2,
                 center_y: height as i32 / 2,
             },
+            texture_creator,
+            text_input,
+            mouse,
+            controller_subsystem,
+            joystick_subsystem,
         };
 
         Ok(renderer)
     }
 
-    pub fn play<'s, EntityState, GameData>(&mut self, scene: &mut Scene<EntityState, GameData>, target_fps: u64)
+    pub fn play<EntityState, GameData>(&mut self, scene: &mut Scene<EntityState, GameData>, target_fps: u64)
          -> Result<(), String> 
     where 
     EntityState: Default + Clone,
     GameData: Default,
     {
-        // create a texture container for the canvas to draw from
-        let texture_creator = self.canvas.borrow_mut().texture_creator();
+        let mut game = self.start(scene, target_fps)?;
+        while game.step()? {}
+        game.finish();
+
+        Ok(())
+    }
+
+    // Sets up a scene without running it, use `GameLoop::step` to run it frame by frame.
+    pub fn start<'r, EntityState, GameData>(&'r mut self, scene: &Scene<EntityState, GameData>, target_fps: u64)
+         -> Result<GameLoop<'r, EntityState, GameData>, String> 
+    where 
+    EntityState: Default + Clone,
+    GameData: Default,
+    {
+        let Renderer { title, event_pump, canvas, screen, texture_creator, text_input, mouse, controller_subsystem, joystick_subsystem } = self;
 
         // setup a render context to talk to while looping though all swarm pool objects
         let mut context = RenderContext { 
-            textures: Vec::<Texture<'s>>::new(),
-            canvas: self.canvas.clone(),
+            textures: Vec::<Texture<'r>>::new(),
+            canvas: canvas.clone(),
             timer: UpdateTimer::new(target_fps),
+            scheduler: Scheduler::new(),
             data: GameData::default(),
             input: Input::new(),
-            camera: Camera { x:0.0, y:0.0, zoom:1.0, zpow: 1.0, },
-            screen: self.screen.clone(),
+            cameras: vec![Camera::new()],
+            screen: screen.clone(),
+            particles: ParticleSystem::new(),
+            lighting: Lighting::new(),
+            active_camera: 0,
+            previous_transforms: vec![None; scene.pool_size],
+            drawn_transforms: Vec::with_capacity(scene.pool_size),
         };
 
         // create texture maps from loaded surfaces
@@ -109,119 +243,317 @@ impl Renderer {
         // tell scene observer, scene initialization is complete
         (scene.on_start)(&mut swarm);
 
-        // start game loop
-        'game_loop: loop {
-
-            // reset frame based events
-            swarm.properties.input.keyboard.releave_activity();
-
-            // capture/handle input events
-            while let Some(event) = self.event_pump.poll_event() {
-                match event {
-                    SdlEvent::Quit{ .. } => break 'game_loop,
-
-                    SdlEvent::KeyDown { keycode, .. } => {
-                        if let Some (key) = keycode { 
-                            input::map_keys(&mut swarm.properties.input.keyboard, key, true);
-                        }
-                    },
-                    SdlEvent::KeyUp { keycode, .. } => {
-                        if let Some (key) = keycode { 
-                            input::map_keys(&mut swarm.properties.input.keyboard, key, false);
-                        }
-                    },
-
-                    SdlEvent::MouseMotion {x, y, ..} => {
-                        swarm.properties.input.mouse.x = x;
-                        swarm.properties.input.mouse.y = y;
-                    },
-                    SdlEvent::MouseButtonUp {mouse_btn, ..} => {
-                        match mouse_btn {
-                            sdl2::mouse::MouseButton::Left => swarm.properties.input.mouse.left_button = false,
-                            sdl2::mouse::MouseButton::Right => swarm.properties.input.mouse.right_button = false,
-                            sdl2::mouse::MouseButton::Middle => swarm.properties.input.mouse.middle_button = false,
-                            _ => {},
-                        };
-                    },
-                    SdlEvent::MouseButtonDown {mouse_btn, ..} => {
-                        match mouse_btn {
-                            sdl2::mouse::MouseButton::Left => swarm.properties.input.mouse.left_button = true,
-                            sdl2::mouse::MouseButton::Right => swarm.properties.input.mouse.right_button = true,
-                            sdl2::mouse::MouseButton::Middle => swarm.properties.input.mouse.middle_button = true,
-                            _ => {},
-                        };
-                    },
-
-                    SdlEvent::ControllerDeviceAdded {which, ..} => {
-                        swarm.properties.input.controllers.add(which);
-                    },
-                    SdlEvent::ControllerDeviceRemoved {which, ..} => {
-                        swarm.properties.input.controllers.remove(which);
-                    },
-                    SdlEvent::ControllerAxisMotion {which, axis, value, ..} => {
-                        swarm.properties.input.controllers.set_axis(which, &axis, value);
-                    },
-                    SdlEvent::ControllerButtonUp {which, button, ..} => {
-                        swarm.properties.input.controllers.set_button(which, &button, false);
-                    },
-                    SdlEvent::ControllerButtonDown {which, button, ..} => {
-                        swarm.properties.input.controllers.set_button(which, &button, true);
-                    },
-                    _ => {},
-                }
+        Ok(GameLoop {
+            swarm,
+            title,
+            event_pump,
+            canvas: canvas.clone(),
+            texture_creator,
+            text_input,
+            mouse,
+            controller_subsystem,
+            joystick_subsystem,
+            // the light map render target is only created once lighting is enabled
+            light_map: None,
+            on_update: scene.on_update,
+            on_end: scene.on_end,
+        })
+    }
+}
+
+impl<'r, EntityState, GameData> GameLoop<'r, EntityState, GameData> 
+where 
+EntityState: Default + Clone,
+{
+    // runs one frame, returns false once the window was asked to close
+    pub fn step(&mut self) -> Result<bool, String> {
+        let update_start = Instant::now();
+        let swarm = &mut self.swarm;
+
+        // start or stop text input when the game asked for it
+        let input = &mut swarm.properties.input;
+        if input.text.active() != self.text_input.is_active() {
+            if input.text.active() {
+                self.text_input.start();
+                input.keyboard.release_all();
+            } else {
+                self.text_input.stop();
+            }
+        }
+        if let Some(area) = input.text.take_area() {
+            self.text_input.set_rect(area);
+        }
+        input.cursor.apply(self.mouse, self.canvas.borrow_mut().window_mut());
+
+        // capture/handle input events
+        while let Some(event) = self.event_pump.poll_event() {
+            match event {
+                SdlEvent::Quit{ .. } => return Ok(false),
+
+                // while typing, key presses only edit the text
+                SdlEvent::KeyDown { keycode, .. } if swarm.properties.input.text.active() => {
+                    match keycode {
+                        Some(Keycode::Backspace) => swarm.properties.input.text.backspace(),
+                        Some(Keycode::Return) | Some(Keycode::KpEnter) => swarm.properties.input.text.submit(),
+                        Some(Keycode::Escape) => swarm.properties.input.text.cancel(),
+                        _ => {},
+                    }
+                },
+                SdlEvent::KeyDown { keycode, scancode, .. } => {
+                    input::map_keys(&mut swarm.properties.input.keyboard, keycode, scancode, true);
+                },
+                SdlEvent::KeyUp { keycode, scancode, .. } => {
+                    input::map_keys(&mut swarm.properties.input.keyboard, keycode, scancode, false);
+                },
+
+                SdlEvent::TextInput { text, .. } if swarm.properties.input.text.active() => {
+                    swarm.properties.input.text.insert(&text);
+                },
+                SdlEvent::TextEditing { text, start, length, .. } => {
+                    swarm.properties.input.text.edit(&text, start, length);
+                },
+
+                SdlEvent::MouseMotion {x, y, xrel, yrel, ..} => {
+                    swarm.properties.input.mouse.set_position(x, y, xrel, yrel);
+                },
+                SdlEvent::MouseButtonUp {mouse_btn, clicks, ..} => {
+                    swarm.properties.input.mouse.set_button(mouse_btn, false, clicks);
+                },
+                SdlEvent::MouseButtonDown {mouse_btn, clicks, ..} => {
+                    swarm.properties.input.mouse.set_button(mouse_btn, true, clicks);
+                },
+                SdlEvent::MouseWheel {x, y, direction, ..} => {
+                    swarm.properties.input.mouse.scroll(x, y, direction);
+                },
+
+                // `which` is a device index here, all other controller events use the instance id
+                SdlEvent::ControllerDeviceAdded {which, ..} => {
+                    if let Ok(controller) = self.controller_subsystem.open(which) {
+                        let guid = self.joystick_subsystem.device_guid(which)
+                            .map(|guid| guid.string())
+                            .unwrap_or_default();
+                        swarm.properties.input.controllers.add(controller, guid);
+                    }
+                },
+                SdlEvent::ControllerDeviceRemoved {which, ..} => {
+                    swarm.properties.input.controllers.remove(which);
+                },
+                SdlEvent::ControllerAxisMotion {which, axis, value, ..} => {
+                    swarm.properties.input.controllers.set_axis(which, &axis, value);
+                },
+                SdlEvent::ControllerButtonUp {which, button, ..} => {
+                    swarm.properties.input.controllers.set_button(which, &button, false);
+                },
+                SdlEvent::ControllerButtonDown {which, button, ..} => {
+                    swarm.properties.input.controllers.set_button(which, &button, true);
+                },
+                _ => {},
             }
+        }
 
-            // tell scene observer to update their frame code
-            (scene.on_update)(&mut swarm);
+        swarm.properties.input.update_actions();
+
+        // fire scheduled timers on game time
+        let game = &mut swarm.properties;
+        game.scheduler.advance(game.timer.game_frame_duration);
+
+        // tell scene observer to update their frame code, either once per frame
+        // or as many fixed steps as fit in the last frame
+        let timer = &swarm.properties.timer;
+        // frame based input and timer events are only reset once an update has seen them
+        if timer.fixed_step().is_some() && !timer.paused {
+            while swarm.properties.timer.next_step() {
+                store_previous_transforms(swarm);
+                (self.on_update)(swarm);
+                swarm.properties.input.releave_activity();
+                swarm.properties.scheduler.releave_activity();
+            }
+        } else {
+            (self.on_update)(swarm);
+            swarm.properties.input.releave_activity();
+            swarm.properties.scheduler.releave_activity();
+        }
+        let alpha = swarm.properties.timer.alpha();
+        interpolate_transforms(swarm, alpha);
+
+        // move cameras along with the entities they follow
+        let focus: Vec<Option<(f32, f32)>> = swarm.properties.cameras.iter()
+            .map(|camera| camera.follow.as_ref()
+                .filter(|follow| follow.target.active())
+                .map(|follow| swarm.fetch_ref(&follow.target).transform
+                    .interpolated_center(swarm.properties.previous_transform(&follow.target), alpha)))
+            .collect();
+        let game = &mut swarm.properties;
+        for (camera, focus) in game.cameras.iter_mut().zip(focus) {
+            camera.update(focus, &game.screen, game.timer.frame_time());
+        }
+
+        // simulate particles, emitters attached to an entity follow its center
+        let mut particles = std::mem::take(&mut swarm.properties.particles);
+        particles.update(swarm.properties.timer.game_frame_time(), |spawn| {
+            if !spawn.active() { return None; }
+            Some(swarm.fetch_ref(spawn).transform.interpolated_center(swarm.properties.previous_transform(spawn), alpha))
+        });
+        swarm.properties.particles = particles;
+
+        // update sprite animations
+        swarm.for_all(|obj_index, pool, game| {
+            pool[*obj_index].sprite.update_animation(&game.timer.game_frame_duration);
+        });
+        
+        let render_start = Instant::now();
 
-            swarm.properties.camera.set_power();
-            
-            // clear screen buffer
-            self.canvas.borrow_mut().clear();
+        // clear screen buffer
+        self.canvas.borrow_mut().clear();
+
+        // write screen buffer, once for every camera
+        for camera_index in 0..swarm.properties.cameras.len() {
+            swarm.properties.active_camera = camera_index;
+            {
+                let game = &swarm.properties;
+                game.canvas.borrow_mut().set_clip_rect(game.cameras[camera_index].view_rect(&game.screen));
+            }
 
-            // write screen buffer
             swarm.for_all(|obj_index, pool, game| {
+                let camera = &game.cameras[game.active_camera];
+                if !camera.shows_layer(pool[*obj_index].sprite.layer) { return; }
+
+                let (x, y, rotation) = game.drawn_transforms[*obj_index];
+
+                if let Some(dst) = &mut pool[*obj_index].sprite.dst.0 {
+                    let transform = &pool[*obj_index].transform;
+                    let (x, y) = (x + transform.width as f32 * 0.5, y + transform.height as f32 * 0.5);
+                    let (x, y) = camera.world_to_screen(&game.screen, x, y);
+                    let scale = camera.scale();
+                    let width = transform.width as f32 * scale;
+                    let height = transform.height as f32 * scale;
+
+                    dst.set_x((x - width * 0.5) as i32);
+                    dst.set_y((y - height * 0.5) as i32);
+                    dst.set_width(width as u32);
+                    dst.set_height(height as u32);
+                }
+
+                game.canvas.borrow_mut().copy_ex(
+                    &game.textures[pool[*obj_index].sprite.texure_id],
+                    pool[*obj_index].sprite.src.0,
+                    pool[*obj_index].sprite.dst.0,
+                    rotation + camera.view_angle(),
+                    None,
+                    pool[*obj_index].transform.flip_horizontal,
+                    pool[*obj_index].transform.flip_vertical,
+                ).unwrap();
+            });
 
-                //let target = &mut pool[*obj_index];
+            // draw particles on top of the entities
+            let game = &mut swarm.properties;
+            let camera = &game.cameras[camera_index];
+            for emitter in game.particles.iter().filter(|e| camera.shows_layer(e.layer)) {
+                let texture = &mut game.textures[emitter.texure_id];
+                let blend_mode = texture.blend_mode();
+                let mut canvas = game.canvas.borrow_mut();
+                let screen = &game.screen;
+
+                texture.set_blend_mode(if emitter.additive { BlendMode::Add } else { BlendMode::Blend });
+
+                emitter.for_each_particle(|x, y, scale, tile, color| {
+                    let (tile_x, tile_y) = emitter.tile_position(tile);
+                    let width = emitter.width as f32 * scale * camera.scale();
+                    let height = emitter.height as f32 * scale * camera.scale();
+                    let (x, y) = camera.world_to_screen(screen, x, y);
+
+                    texture.set_color_mod(color.r, color.g, color.b);
+                    texture.set_alpha_mod(color.a);
+                    canvas.copy(
+                        texture,
+                        Rect::new(tile_x, tile_y, emitter.tile_size.width, emitter.tile_size.height),
+                        Rect::new((x - width * 0.5) as i32, (y - height * 0.5) as i32, width as u32, height as u32),
+                    ).unwrap();
+                });
 
-                // if let Some(sprite) = &mut target.sprite
-                // {
-                    pool[*obj_index].sprite.update_animation(&game.timer.frame_duration);
+                texture.set_color_mod(255, 255, 255);
+                texture.set_alpha_mod(255);
+                texture.set_blend_mode(blend_mode);
+            }
 
-                    if let Some(dst) = &mut pool[*obj_index].sprite.dst.0 {
-                        let x = pool[*obj_index].transform.x - game.camera.x;
-                        let y = pool[*obj_index].transform.y - game.camera.y;
-                        
+            // multiply the light map over the world
+            if game.lighting.enabled && camera.lit {
+                if self.light_map.is_none() {
+                    self.light_map = Some(LightMap::new(self.texture_creator, &game.screen)?);
+                }
+                if let Some(light_map) = &mut self.light_map {
+                    light_map.render(
+                        self.texture_creator,
+                        &mut self.canvas.borrow_mut(),
+                        &game.lighting,
+                        camera,
+                        &game.screen,
+                    )?;
+                }
+            }
+        }
+        self.canvas.borrow_mut().set_clip_rect(None);
+
+        // present screen buffer
+        let present_start = Instant::now();
+        self.canvas.borrow_mut().present();
+
+        let stats = &mut swarm.properties.timer.stats;
+        stats.update_time = render_start - update_start;
+        stats.render_time = present_start - render_start;
+        stats.present_time = present_start.elapsed();
+
+        // update frame timer
+        swarm.properties.timer.sync();
+
+        let stats = &mut swarm.properties.timer.stats;
+        if let Some(text) = stats.overlay_text() {
+            match stats.overlay {
+                StatsOverlay::Log => println!("{}", text),
+                StatsOverlay::WindowTitle => {
+                    self.canvas.borrow_mut().window_mut()
+                        .set_title(&format!("{} | {}", self.title, text))
+                        .map_err(|e| e.to_string())?;
+                },
+                StatsOverlay::Off => {},
+            }
+        }
 
-                        dst.set_x(game.screen.center_x + (x * game.camera.zpow) as i32);
-                        dst.set_y(game.screen.center_y + (y * game.camera.zpow) as i32);
-                        dst.set_width((pool[*obj_index].transform.width as f32 * game.camera.zpow) as u32);
-                        dst.set_height((pool[*obj_index].transform.height as f32 * game.camera.zpow) as u32);
-                    }
+        Ok(true)
+    }
 
-                    game.canvas.borrow_mut().copy_ex(
-                        &game.textures[pool[*obj_index].sprite.texure_id],
-                        pool[*obj_index].sprite.src.0,
-                        pool[*obj_index].sprite.dst.0,
-                        pool[*obj_index].transform.rotation,
-                        None,
-                        pool[*obj_index].transform.flip_horizontal,
-                        pool[*obj_index].transform.flip_vertical,
-                    ).unwrap();
-               // }
+    // tell the scene observer the scene has finisched
+    pub fn finish(self) {
+        (self.on_end)();
+    }
+}
 
-            });
+type EntitySwarm<'r, EntityState, GameData> = Swarm<Entity<EntityState>, RenderContext<'r, GameData>>;
 
-            // present screen buffer
-            self.canvas.borrow_mut().present();
+// remembers where every entity was before a fixed update, killed entities are forgotten
+fn store_previous_transforms<EntityState: Default + Clone, GameData>(swarm: &mut EntitySwarm<EntityState, GameData>) {
+    let mut previous = std::mem::take(&mut swarm.properties.previous_transforms);
+    previous.fill(None);
 
-            // update frame timer
-            swarm.properties.timer.sync();
+    for pos in 0..swarm.count() {
+        let id = swarm.fetch_spawn(&pos).id();
+        let transform = &swarm.fetch_raw(&pos).transform;
+        if let Some(previous) = previous.get_mut(id) {
+            *previous = Some((transform.x, transform.y, transform.rotation));
         }
+    }
+    swarm.properties.previous_transforms = previous;
+}
 
-        // tell the scene observer the scene has finisched
-        (scene.on_end)();
+// blends every entity between its previous and current fixed update once per frame, 
+// so every camera draws the same positions
+fn interpolate_transforms<EntityState: Default + Clone, GameData>(swarm: &mut EntitySwarm<EntityState, GameData>, alpha: f32) {
+    let mut drawn = std::mem::take(&mut swarm.properties.drawn_transforms);
+    drawn.clear();
 
-        Ok(())
+    for pos in 0..swarm.count() {
+        let previous = swarm.properties.previous_transform(&swarm.fetch_spawn(&pos));
+        drawn.push(swarm.fetch_raw(&pos).transform.interpolate(previous, alpha));
     }
-}
\ No newline at end of file
+    swarm.properties.drawn_transforms = drawn;
+}
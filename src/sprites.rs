@@ -1,4 +1,5 @@
 use std::ops::RangeInclusive;
+use std::time::Duration;
 
 use sdl2::rect::Rect;
 
@@ -36,6 +37,7 @@ impl SpriteBuilder {
             num_tile_cols: 4,
             animation: 0,
             animations: Vec::new(),
+            layer: 0,
 
             src: PoolRect::default(),
             dst: PoolRect::default()
@@ -58,6 +60,10 @@ impl SpriteBuilder {
         self.0.animations = animations;
         self
     }
+    pub fn with_layer(mut self, layer: u8)-> Self {
+        self.0.layer = layer;
+        self
+    }
     pub fn build(self) -> Sprite {
         self.0
     }
@@ -72,6 +78,8 @@ pub struct Sprite {
     pub num_tile_cols: u16,
     pub animation: usize,
     pub animations: Vec<SpriteAnimation>,
+    // cameras only draw sprites on the layers in their layer mask (0 - 31)
+    pub layer: u8,
 
     pub(crate) src: PoolRect,
     pub(crate) dst: PoolRect,
@@ -97,17 +105,16 @@ impl Sprite {
     //     }
     // }
 
-    pub fn update_animation(&mut self, frame_dration: &u32) {
+    pub fn update_animation(&mut self, frame_duration: &Duration) {
         if self.animation < self.animations.len() {
 
             // increment animation frame
             let anim = &mut self.animations[self.animation];
             
-            anim.millis_passed += *frame_dration; //(*_delta_time * 1_000.0) as u64;
-            anim.millis_passed = anim.millis_passed % 
-                (anim.millis_per_frame * anim.tile_range.len() as u32);
+            anim.millis_passed += frame_duration.as_secs_f32() * 1_000.0;
+            anim.millis_passed %= anim.millis_per_frame as f32 * anim.tile_range.len() as f32;
 
-            anim.current_frame = (anim.millis_passed / anim.millis_per_frame) as u16;
+            anim.current_frame = (anim.millis_passed / anim.millis_per_frame as f32) as u16;
 
             // set tile position
             let tile = self.animations[self.animation].tile_range.start() 
@@ -132,5 +139,5 @@ pub struct SpriteAnimation {
     pub current_frame: u16,
     pub tile_range: RangeInclusive<u16>,
     pub millis_per_frame: u32,
-    pub millis_passed: u32,
+    pub millis_passed: f32,
 }
\ No newline at end of file
@@ -0,0 +1,270 @@
+
+mod scheduler;
+mod stats;
+
+use std::time::{ Duration, Instant };
+pub use scheduler::{ Scheduler, TimerEvent, TimerHandle };
+pub use stats::{ FrameStats, StatsOverlay };
+
+pub struct UpdateTimer {
+    frame_start: Instant,
+    target_time: Duration,
+    spin_margin: Duration,
+    manual_step: Option<Duration>,
+
+    // scaled game time of the current update in seconds, 0.0 while paused
+    pub delta_time: f32,
+    // unscaled duration of the last frame in seconds
+    pub real_delta_time: f32,
+    pub frame_duration: Duration,
+    pub game_frame_duration: Duration,
+    pub stats: FrameStats,
+
+    // multiplies game time, 0.5 runs the game at half speed
+    pub time_scale: f32,
+    // stops game time, on_update still runs so input and menus keep working
+    pub paused: bool,
+    real_time: Duration,
+    game_time: Duration,
+
+    fixed_step: Option<f32>,
+    max_steps: u32,
+    accumulator: f32,
+    // no fixed update ran since the fixed step was set, nothing to interpolate from yet
+    stepped: bool,
+}
+
+impl UpdateTimer {
+    pub fn new(target_fps: u64) -> Self {
+        UpdateTimer {
+            frame_start: Instant::now(),
+            target_time: target_time(target_fps),
+            spin_margin: Duration::from_millis(1),
+            manual_step: None,
+            delta_time: 0.0,
+            real_delta_time: 0.0,
+            frame_duration: Duration::from_secs(0),
+            game_frame_duration: Duration::from_secs(0),
+            stats: FrameStats::new(),
+
+            time_scale: 1.0,
+            paused: false,
+            real_time: Duration::from_secs(0),
+            game_time: Duration::from_secs(0),
+
+            fixed_step: None,
+            max_steps: 5,
+            accumulator: 0.0,
+            stepped: false,
+        }
+    }
+
+    // A timer that never reads the wall clock or sleeps, every frame takes exactly 
+    // `step`. Runs with the same steps produce identical delta times and animations.
+    pub fn manual(step: Duration) -> Self {
+        let mut timer = UpdateTimer::new(0);
+        timer.manual_step = Some(step);
+        timer
+    }
+
+    // switches between a manual clock with a fixed frame duration and the wall clock
+    pub fn set_manual_step(&mut self, step: Option<Duration>) {
+        self.manual_step = step;
+        self.frame_start = Instant::now();
+    }
+
+    pub fn is_manual(&self) -> bool {
+        self.manual_step.is_some()
+    }
+
+    // use 0 to run without a frame limit
+    pub fn set_target_fps(&mut self, target_fps: u64) {
+        self.target_time = target_time(target_fps);
+    }
+
+    // Frames sleep until this long before their target time and busy wait for the rest,
+    // since sleeping can overshoot by a millisecond or more on most systems.
+    pub fn set_spin_margin(&mut self, spin_margin: Duration) {
+        self.spin_margin = spin_margin;
+    }
+
+    // Runs on_update at a fixed rate, independent of the frame rate. Depending on how 
+    // long a frame took, on_update runs zero or more times per rendered frame with 
+    // `delta_time` set to the fixed step. Use None to update once per frame again.
+    pub fn set_fixed_step(&mut self, updates_per_second: Option<u32>) {
+        self.fixed_step = updates_per_second.map(|ups| 1.0 / ups.max(1) as f32);
+        self.accumulator = 0.0;
+        self.stepped = false;
+    }
+
+    // the most updates to run in one frame, slow frames drop the time that is left over
+    pub fn set_max_steps(&mut self, max_steps: u32) {
+        self.max_steps = max_steps.max(1);
+    }
+
+    pub fn fixed_step(&self) -> Option<f32> {
+        self.fixed_step
+    }
+
+    // the duration of the last frame in seconds
+    pub fn frame_time(&self) -> f32 {
+        self.frame_duration.as_secs_f32()
+    }
+
+    // the scaled game time the last frame took in seconds
+    pub fn game_frame_time(&self) -> f32 {
+        self.game_frame_duration.as_secs_f32()
+    }
+
+    // time passed since the timer started
+    pub fn real_time(&self) -> Duration {
+        self.real_time
+    }
+
+    // scaled time passed since the timer started, without the time spent paused
+    pub fn game_time(&self) -> Duration {
+        self.game_time
+    }
+
+    pub fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+    }
+
+    // How far rendering is between the previous and the current fixed update, from 0.0 
+    // to 1.0. Always 1.0 without a fixed step.
+    pub fn alpha(&self) -> f32 {
+        match self.fixed_step {
+            Some(step) if self.stepped => (self.accumulator / step).min(1.0),
+            _ => 1.0,
+        }
+    }
+
+    // takes one fixed step from the accumulated frame time, returns false when 
+    // there is not enough time left for another update
+    pub(crate) fn next_step(&mut self) -> bool {
+        match self.fixed_step {
+            Some(step) if self.accumulator >= step => {
+                self.accumulator -= step;
+                self.delta_time = step;
+                self.stepped = true;
+                true
+            },
+            _ => false,
+        }
+    }
+
+    pub fn sync(&mut self) {
+        match self.manual_step {
+            Some(step) => self.advance(step),
+            None => {
+                self.wait_for_target();
+
+                let now = Instant::now();
+                let frame = now - self.frame_start;
+                self.frame_start = now;
+                self.advance(frame);
+            },
+        }
+    }
+
+    // Ends the frame as if `frame` amount of time has passed, without waiting. This is 
+    // what sync does with a manual clock, and can be used to step a manual clock by
+    // varying amounts.
+    pub fn advance(&mut self, frame: Duration) {
+        self.frame_duration = frame;
+
+        let scale = if self.paused { 0.0 } else { self.time_scale.max(0.0) };
+        self.game_frame_duration = self.frame_duration.mul_f32(scale);
+        self.real_time += self.frame_duration;
+        self.game_time += self.game_frame_duration;
+
+        self.real_delta_time = self.frame_duration.as_secs_f32();
+        self.delta_time = self.game_frame_duration.as_secs_f32(); 
+
+        if let Some(step) = self.fixed_step {
+            self.accumulator = (self.accumulator + self.delta_time).min(step * self.max_steps as f32);
+        }
+
+        self.stats.record(self.frame_duration);
+    }
+
+    // sleep for most of the time that is left and spin for the last part
+    fn wait_for_target(&self) {
+        let target = self.frame_start + self.target_time;
+        let now = Instant::now();
+
+        if target > now {
+            let remaining = target - now;
+            if remaining > self.spin_margin {
+                std::thread::sleep(remaining - self.spin_margin);
+            }
+            while Instant::now() < target {
+                std::hint::spin_loop();
+            }
+        }
+    }
+}
+
+
+fn target_time(target_fps: u64) -> Duration {
+    if target_fps == 0 {
+        Duration::from_secs(0)
+    } else {
+        Duration::from_secs_f64(1.0 / target_fps as f64)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::UpdateTimer;
+    use crate::sprites::{ SpriteAnimation, SpriteBuilder };
+
+    fn walk_animation() -> SpriteAnimation {
+        SpriteAnimation { current_frame: 0, tile_range: 0..=3, millis_per_frame: 80, millis_passed: 0.0 }
+    }
+
+    // runs a manual timer and an animated sprite for a number of frames, returns the
+    // delta time, game time and animation frame of every frame
+    fn run(frames: usize) -> Vec<(f32, Duration, u16)> {
+        let mut timer = UpdateTimer::manual(Duration::from_micros(16_667));
+        timer.time_scale = 0.75;
+        let mut sprite = SpriteBuilder::new(0)
+            .with_tile_size(32, 32)
+            .with_animations(vec![walk_animation()])
+            .build();
+
+        let mut results = Vec::with_capacity(frames);
+        for frame in 0..frames {
+            if frame % 7 == 0 {
+                timer.advance(Duration::from_millis(33));
+            } else {
+                timer.sync();
+            }
+            sprite.update_animation(&timer.game_frame_duration);
+            results.push((timer.delta_time, timer.game_time(), sprite.animations[0].current_frame));
+        }
+        results
+    }
+
+    #[test]
+    fn manual_timers_are_deterministic() {
+        let first = run(240);
+        let second = run(240);
+        assert_eq!(first, second);
+
+        // the animation actually moved through its frames
+        assert!(first.iter().any(|r| r.2 != 0));
+        assert_eq!(first[1].0, Duration::from_micros(16_667).mul_f32(0.75).as_secs_f32());
+    }
+
+    #[test]
+    fn manual_timer_never_reads_the_clock() {
+        let mut timer = UpdateTimer::manual(Duration::from_millis(10));
+        for _ in 0..100 { timer.sync(); }
+        assert_eq!(timer.real_time(), Duration::from_secs(1));
+        assert_eq!(timer.game_time(), Duration::from_secs(1));
+    }
+}
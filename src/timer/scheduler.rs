@@ -0,0 +1,126 @@
+use std::time::Duration;
+
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct TimerHandle(u64);
+
+
+// `event` is the value the timer was registered with
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct TimerEvent {
+    pub handle: TimerHandle,
+    pub event: usize,
+}
+
+
+struct ScheduledTimer {
+    handle: TimerHandle,
+    event: usize,
+    remaining: Duration,
+    interval: Option<Duration>,
+}
+
+
+// Timers run on game time, so they stop while the game is paused and follow 
+// the time scale. Fired timers can be polled during the next on_update.
+pub struct Scheduler {
+    timers: Vec<ScheduledTimer>,
+    fired: Vec<TimerEvent>,
+    next_id: u64,
+}
+
+impl Scheduler {
+    pub fn new() -> Self {
+        Scheduler { timers: Vec::new(), fired: Vec::new(), next_id: 0 }
+    }
+
+    // fires `event` once after `delay` seconds
+    pub fn after(&mut self, delay: f32, event: usize) -> TimerHandle {
+        self.add(seconds(delay.max(0.0)), None, event)
+    }
+
+    // fires `event` every `interval` seconds until cancelled
+    pub fn every(&mut self, interval: f32, event: usize) -> TimerHandle {
+        let interval = seconds(interval.max(0.001));
+        self.add(interval, Some(interval), event)
+    }
+
+    // returns false if the timer already finished or was cancelled before
+    pub fn cancel(&mut self, handle: TimerHandle) -> bool {
+        match self.timers.iter().position(|t| t.handle == handle) {
+            Some(index) => { self.timers.remove(index); true },
+            None => false,
+        }
+    }
+
+    pub fn cancel_all(&mut self) {
+        self.timers.clear();
+    }
+
+    pub fn is_active(&self, handle: TimerHandle) -> bool {
+        self.timers.iter().any(|t| t.handle == handle)
+    }
+
+    // seconds left until the timer fires next
+    pub fn remaining(&self, handle: TimerHandle) -> Option<f32> {
+        self.timers.iter().find(|t| t.handle == handle).map(|t| t.remaining.as_secs_f32())
+    }
+
+    // takes the next fired timer event that was not polled yet
+    pub fn poll(&mut self) -> Option<TimerEvent> {
+        if self.fired.is_empty() { None } else { Some(self.fired.remove(0)) }
+    }
+
+    // returns true if the timer fired since the last update, without taking its event
+    pub fn fired(&self, handle: TimerHandle) -> bool {
+        self.fired.iter().any(|e| e.handle == handle)
+    }
+
+    pub fn events(&self) -> impl Iterator<Item = &TimerEvent> {
+        self.fired.iter()
+    }
+
+    fn add(&mut self, delay: Duration, interval: Option<Duration>, event: usize) -> TimerHandle {
+        let handle = TimerHandle(self.next_id);
+        self.next_id += 1;
+        self.timers.push(ScheduledTimer { handle, event, remaining: delay, interval });
+        handle
+    }
+
+    // fired events are kept until `releave_activity`, so frames without an update
+    // pass them on to the next update
+    pub(crate) fn advance(&mut self, game_time: Duration) {
+        if game_time == Duration::from_secs(0) { return; }
+
+        let fired = &mut self.fired;
+        self.timers.retain_mut(|timer| {
+            let mut elapsed = game_time;
+
+            while elapsed >= timer.remaining {
+                elapsed -= timer.remaining;
+                fired.push(TimerEvent { handle: timer.handle, event: timer.event });
+
+                match timer.interval {
+                    Some(interval) => timer.remaining = interval,
+                    None => return false,
+                }
+            }
+            timer.remaining -= elapsed;
+            true
+        });
+    }
+
+    // drops the fired events once an update had the chance to poll them
+    pub(crate) fn releave_activity(&mut self) {
+        self.fired.clear();
+    }
+}
+
+// delays too long for a Duration, like f32::INFINITY, never fire
+fn seconds(value: f32) -> Duration {
+    Duration::try_from_secs_f32(value).unwrap_or(Duration::MAX)
+}
+
+impl Default for Scheduler {
+    fn default() -> Self { Scheduler::new() }
+}
@@ -0,0 +1,116 @@
+use std::time::Duration;
+
+
+const SAMPLE_COUNT: usize = 240;
+
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum StatsOverlay {
+    Off,
+    // prints the stats to stdout
+    Log,
+    // shows the stats in the window title
+    WindowTitle,
+}
+
+
+// Timing of the most recent frames, kept in a ring buffer.
+pub struct FrameStats {
+    samples: Vec<Duration>,
+    next: usize,
+
+    // time spent on the last frame's input, updates and simulation
+    pub update_time: Duration,
+    // time spent on the last frame's drawing
+    pub render_time: Duration,
+    // time spent on presenting the last frame
+    pub present_time: Duration,
+
+    pub overlay: StatsOverlay,
+    pub overlay_interval: Duration,
+    since_overlay: Duration,
+}
+
+impl FrameStats {
+    pub fn new() -> Self {
+        FrameStats {
+            samples: Vec::with_capacity(SAMPLE_COUNT),
+            next: 0,
+
+            update_time: Duration::from_secs(0),
+            render_time: Duration::from_secs(0),
+            present_time: Duration::from_secs(0),
+
+            overlay: StatsOverlay::Off,
+            overlay_interval: Duration::from_secs(1),
+            since_overlay: Duration::from_secs(0),
+        }
+    }
+
+    // duration of the last frame
+    pub fn current(&self) -> Duration {
+        match self.samples.len() {
+            0 => Duration::from_secs(0),
+            len => self.samples[(self.next + len - 1) % len],
+        }
+    }
+
+    pub fn average(&self) -> Duration {
+        if self.samples.is_empty() { return Duration::from_secs(0); }
+        self.samples.iter().sum::<Duration>() / self.samples.len() as u32
+    }
+
+    // the frame time that `percent` of the recent frames stayed under, 
+    // percentile(99.0) shows the slow frames that cause stutter
+    pub fn percentile(&self, percent: f32) -> Duration {
+        if self.samples.is_empty() { return Duration::from_secs(0); }
+
+        let mut sorted = self.samples.clone();
+        sorted.sort();
+        let index = ((percent.clamp(0.0, 100.0) / 100.0) * (sorted.len() - 1) as f32).round();
+        sorted[index as usize]
+    }
+
+    pub fn average_fps(&self) -> f32 {
+        match self.average().as_secs_f32() {
+            time if time > 0.0 => 1.0 / time,
+            _ => 0.0,
+        }
+    }
+
+    pub fn sample_count(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub(crate) fn record(&mut self, frame: Duration) {
+        if self.samples.len() < SAMPLE_COUNT {
+            self.samples.push(frame);
+        } else {
+            self.samples[self.next] = frame;
+        }
+        self.next = (self.next + 1) % SAMPLE_COUNT;
+        self.since_overlay += frame;
+    }
+
+    // returns the overlay text once every overlay interval, if the overlay is enabled
+    pub(crate) fn overlay_text(&mut self) -> Option<String> {
+        if self.overlay == StatsOverlay::Off || self.since_overlay < self.overlay_interval {
+            return None;
+        }
+        self.since_overlay = Duration::from_secs(0);
+
+        Some(format!(
+            "fps: {:.1} | frame: {:.2}ms avg, {:.2}ms 99% | update: {:.2}ms | render: {:.2}ms | present: {:.2}ms",
+            self.average_fps(),
+            self.average().as_secs_f64() * 1_000.0,
+            self.percentile(99.0).as_secs_f64() * 1_000.0,
+            self.update_time.as_secs_f64() * 1_000.0,
+            self.render_time.as_secs_f64() * 1_000.0,
+            self.present_time.as_secs_f64() * 1_000.0,
+        ))
+    }
+}
+
+impl Default for FrameStats {
+    fn default() -> Self { FrameStats::new() }
+}
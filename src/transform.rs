@@ -39,6 +39,27 @@ impl Transform {
         self.flip_vertical = true;
         self
     }
+    pub fn center(&self) -> (f32, f32) {
+        (self.x + self.width as f32 * 0.5, self.y + self.height as f32 * 0.5)
+    }
+
+    // blends from the (x, y, rotation) before the last fixed update towards this transform,
+    // see `UpdateTimer::alpha` and `RenderContext::previous_transform`
+    pub fn interpolate(&self, previous: Option<(f32, f32, f64)>, alpha: f32) -> (f32, f32, f64) {
+        match previous {
+            Some((x, y, rotation)) => (
+                x + (self.x - x) * alpha,
+                y + (self.y - y) * alpha,
+                rotation + (self.rotation - rotation) * alpha as f64,
+            ),
+            None => (self.x, self.y, self.rotation),
+        }
+    }
+
+    pub fn interpolated_center(&self, previous: Option<(f32, f32, f64)>, alpha: f32) -> (f32, f32) {
+        let (x, y, _) = self.interpolate(previous, alpha);
+        (x + self.width as f32 * 0.5, y + self.height as f32 * 0.5)
+    }
 }
 
 // #[derive(Default, Clone)]